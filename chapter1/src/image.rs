@@ -1,16 +1,91 @@
+pub mod animation;
+pub mod antialias;
+pub mod atlas;
+pub mod bcn;
+pub mod blue_noise;
+pub mod cubemap;
+pub mod draw;
+pub mod filter;
+pub mod lens;
+pub mod lut;
+pub mod netpbm;
+pub mod noise;
+pub mod normal_map;
+pub mod region;
+pub mod sdf;
+pub mod seam;
+pub mod sharpen;
+pub mod stylize;
+pub mod text;
+pub mod tiled;
+pub mod view;
+
 use image::{DynamicImage, GenericImageView, ImageBuffer, ImageReader, Rgba, RgbaImage};
 
-struct Image {
+#[derive(Clone)]
+pub struct Image {
   width: u32,
   height: u32,
   channels: u8,
   pixels: Vec<[f32; 4]>,
 }
 
+// 이미지 범위를 벗어난 좌표를 처리하는 방식
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BorderMode {
+  // 가장자리 픽셀로 고정
+  Clamp,
+  // 반대편으로 감싸기 (타일링 텍스처)
+  Wrap,
+}
+
 const GAUSSIAN_WEIGHTS: [f32; 5] = [0.0545, 0.2442, 0.4026, 0.2442, 0.0545];
 
+// Rec.709 휘도 (선형 RGB)
+pub(super) fn luminance(p: &[f32; 4]) -> f32 {
+  0.2126 * p[0] + 0.7152 * p[1] + 0.0722 * p[2]
+}
+
+// [0, 1] 값을 반올림해서 8비트로 저장
+pub(super) fn to_u8(value: f32) -> u8 {
+  (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
 impl Image {
-  fn read_from_file(filename: &str) -> Self {
+  pub fn new(width: u32, height: u32) -> Self {
+    Self {
+      width,
+      height,
+      channels: 4,
+      pixels: vec![[0.0; 4]; (width * height) as usize],
+    }
+  }
+
+  // 래스터라이저가 만든 프레임 버퍼처럼 행 우선 RGBA 픽셀로 이미지를 만듭니다.
+  pub fn from_pixels(width: u32, height: u32, pixels: Vec<[f32; 4]>) -> Self {
+    assert_eq!(
+      pixels.len(),
+      (width * height) as usize,
+      "Pixel count mismatch"
+    );
+    Self {
+      width,
+      height,
+      channels: 4,
+      pixels,
+    }
+  }
+
+  pub fn width(&self) -> u32 {
+    self.width
+  }
+
+  pub fn height(&self) -> u32 {
+    self.height
+  }
+
+  #[allow(dead_code)]
+  pub fn read_from_file(filename: &str) -> Self {
     let img = ImageReader::open(filename)
       .expect("Failed to open file")
       .decode()
@@ -55,7 +130,8 @@ impl Image {
     }
   }
 
-  fn write_png(&self, filename: &str) {
+  #[allow(dead_code)]
+  pub fn write_png(&self, filename: &str) {
     let mut img: RgbaImage = ImageBuffer::new(self.width, self.height);
 
     for (x, y, pixel) in img.enumerate_pixels_mut() {
@@ -73,7 +149,7 @@ impl Image {
     img.save(filename).expect("Failed to save PNG file");
   }
 
-  fn get_pixel(&self, i: i32, j: i32) -> &[f32; 4] {
+  pub fn get_pixel(&self, i: i32, j: i32) -> &[f32; 4] {
     let i = i.clamp(0, self.width as i32 - 1);
     let j = j.clamp(0, self.height as i32 - 1);
 
@@ -81,11 +157,39 @@ impl Image {
     &self.pixels[index]
   }
 
-  fn get_pixel_mut(&mut self, i: i32, j: i32) -> &mut [f32; 4] {
+  pub fn get_pixel_mut(&mut self, i: i32, j: i32) -> &mut [f32; 4] {
     let i = i.clamp(0, self.width as i32 - 1);
     let j = j.clamp(0, self.height as i32 - 1);
 
     let index = (i + self.width as i32 * j) as usize;
     &mut self.pixels[index]
   }
+
+  pub fn get_pixel_border(&self, i: i32, j: i32, border: BorderMode) -> &[f32; 4] {
+    match border {
+      BorderMode::Clamp => self.get_pixel(i, j),
+      BorderMode::Wrap => self.get_pixel(
+        i.rem_euclid(self.width as i32),
+        j.rem_euclid(self.height as i32),
+      ),
+    }
+  }
+
+  // 픽셀 좌표 (x, y)에서 쌍선형(bilinear) 보간, 정수 좌표가 픽셀 중심입니다.
+  pub fn sample_bilinear(&self, x: f32, y: f32, border: BorderMode) -> [f32; 4] {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (tx, ty) = (x - x0, y - y0);
+    let (i, j) = (x0 as i32, y0 as i32);
+
+    let p00 = self.get_pixel_border(i, j, border);
+    let p10 = self.get_pixel_border(i + 1, j, border);
+    let p01 = self.get_pixel_border(i, j + 1, border);
+    let p11 = self.get_pixel_border(i + 1, j + 1, border);
+
+    std::array::from_fn(|c| {
+      let top = p00[c] + (p10[c] - p00[c]) * tx;
+      let bottom = p01[c] + (p11[c] - p01[c]) * tx;
+      top + (bottom - top) * ty
+    })
+  }
 }
@@ -0,0 +1,410 @@
+use super::{to_u8, Image};
+
+// 반복 재생 횟수
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Looping {
+  Infinite,
+  // 처음 재생을 포함한 전체 재생 횟수 (1 이상)
+  Times(u16),
+}
+
+impl Looping {
+  // 전체 재생 횟수, 무한 반복이면 None
+  fn plays(self) -> Option<u16> {
+    match self {
+      Looping::Infinite => None,
+      Looping::Times(n) => {
+        assert!(n > 0, "Looping::Times must play at least once");
+        Some(n)
+      }
+    }
+  }
+}
+
+#[derive(Clone)]
+pub struct AnimationFrame {
+  pub image: Image,
+  pub delay_ms: u32,
+}
+
+// 모든 프레임의 크기가 같은 애니메이션 (GIF, APNG로 저장)
+#[derive(Clone)]
+pub struct Animation {
+  pub frames: Vec<AnimationFrame>,
+  pub looping: Looping,
+}
+
+// 팔레트 양자화에 사용하는 RGB 5비트 히스토그램 빈
+const BINS: usize = 1 << 15;
+
+// GIF에서 투명 픽셀에 예약한 팔레트 인덱스
+const TRANSPARENT_INDEX: u8 = 255;
+
+fn bin_index(pixel: &[f32; 4]) -> usize {
+  let q = |v: f32| ((v.clamp(0.0, 1.0) * 31.0).round() as usize) & 31;
+  (q(pixel[0]) << 10) | (q(pixel[1]) << 5) | q(pixel[2])
+}
+
+// 히스토그램 빈 하나에 모인 색 (가중 평균, 픽셀 수)
+#[derive(Clone, Copy)]
+struct ColorCount {
+  color: [f32; 3],
+  count: u32,
+}
+
+// Median cut: 색이 가장 넓게 퍼진 상자를 그 축의 가중 중앙값에서 반복해서 나눕니다.
+fn median_cut(colors: Vec<ColorCount>, max_colors: usize) -> Vec<[f32; 3]> {
+  let mut boxes = vec![colors];
+
+  while boxes.len() < max_colors {
+    // (상자 인덱스, 축, 범위)
+    let mut widest: Option<(usize, usize, f32)> = None;
+    for (b, colors) in boxes.iter().enumerate().filter(|(_, c)| c.len() > 1) {
+      for axis in 0..3 {
+        let (min, max) = colors.iter().fold((f32::MAX, f32::MIN), |(min, max), c| {
+          (min.min(c.color[axis]), max.max(c.color[axis]))
+        });
+        if widest.is_none_or(|(_, _, range)| max - min > range) {
+          widest = Some((b, axis, max - min));
+        }
+      }
+    }
+
+    let Some((b, axis, _)) = widest else {
+      break;
+    };
+
+    let mut colors = boxes.swap_remove(b);
+    colors.sort_by(|x, y| x.color[axis].total_cmp(&y.color[axis]));
+    let total: u32 = colors.iter().map(|c| c.count).sum();
+    let mut acc = 0;
+    let mut split = colors.len() - 1;
+    for (k, c) in colors.iter().enumerate() {
+      acc += c.count;
+      if acc * 2 >= total {
+        split = k + 1;
+        break;
+      }
+    }
+    let split = split.clamp(1, colors.len() - 1);
+
+    let upper = colors.split_off(split);
+    boxes.push(colors);
+    boxes.push(upper);
+  }
+
+  boxes
+    .iter()
+    .map(|colors| {
+      let total: f32 = colors.iter().map(|c| c.count as f32).sum();
+      let mut sum = [0.0; 3];
+      for c in colors {
+        for (s, v) in sum.iter_mut().zip(c.color) {
+          *s += v * c.count as f32;
+        }
+      }
+      sum.map(|s| s / total)
+    })
+    .collect()
+}
+
+// 모든 프레임의 불투명 픽셀로 하나의 전역 팔레트를 만들고 (프레임마다 색이 바뀌는 깜빡임 방지)
+// 각 히스토그램 빈이 가장 가까운 팔레트 색의 인덱스를 함께 반환합니다.
+fn quantize<'a>(
+  images: impl Iterator<Item = &'a Image>, max_colors: usize,
+) -> (Vec<[f32; 3]>, Vec<u8>) {
+  let mut histogram = vec![
+    ColorCount {
+      color: [0.0; 3],
+      count: 0,
+    };
+    BINS
+  ];
+
+  for pixel in images.flat_map(|image| image.pixels.iter()) {
+    if pixel[3] < 0.5 {
+      continue;
+    }
+    let bin = &mut histogram[bin_index(pixel)];
+    for (sum, v) in bin.color.iter_mut().zip(pixel) {
+      *sum += v.clamp(0.0, 1.0);
+    }
+    bin.count += 1;
+  }
+
+  let colors: Vec<ColorCount> = histogram
+    .iter()
+    .filter(|bin| bin.count > 0)
+    .map(|bin| ColorCount {
+      color: bin.color.map(|v| v / bin.count as f32),
+      count: bin.count,
+    })
+    .collect();
+  if colors.is_empty() {
+    return (vec![[0.0; 3]], vec![0; BINS]);
+  }
+
+  let palette = median_cut(colors, max_colors);
+  let lookup = histogram
+    .iter()
+    .map(|bin| {
+      if bin.count == 0 {
+        return 0;
+      }
+      let color = bin.color.map(|v| v / bin.count as f32);
+      let distance = |p: &[f32; 3]| (0..3).map(|c| (p[c] - color[c]).powi(2)).sum::<f32>();
+      (0..palette.len())
+        .min_by(|&a, &b| distance(&palette[a]).total_cmp(&distance(&palette[b])))
+        .unwrap() as u8
+    })
+    .collect();
+
+  (palette, lookup)
+}
+
+impl Animation {
+  pub fn new(looping: Looping) -> Self {
+    Self {
+      frames: Vec::new(),
+      looping,
+    }
+  }
+
+  // 모든 프레임에 같은 지연 시간을 사용
+  pub fn from_images(images: Vec<Image>, delay_ms: u32, looping: Looping) -> Self {
+    Self {
+      frames: images
+        .into_iter()
+        .map(|image| AnimationFrame { image, delay_ms })
+        .collect(),
+      looping,
+    }
+  }
+
+  pub fn push(&mut self, image: Image, delay_ms: u32) {
+    self.frames.push(AnimationFrame { image, delay_ms });
+  }
+
+  fn size(&self) -> (u32, u32) {
+    let first = &self.frames.first().expect("Animation has no frames").image;
+    assert!(
+      self
+        .frames
+        .iter()
+        .all(|f| f.image.width == first.width && f.image.height == first.height),
+      "All frames must have the same size"
+    );
+    (first.width, first.height)
+  }
+
+  // GIF는 최대 256색 팔레트만 사용할 수 있으므로 median cut으로 255색을 고르고,
+  // 알파가 0.5 미만인 픽셀은 남은 한 인덱스로 투명하게 저장합니다.
+  // 지연 시간은 1/100초 단위로 반올림됩니다.
+  pub fn encode_gif(&self) -> Vec<u8> {
+    let (width, height) = self.size();
+    let (width, height) = (
+      u16::try_from(width).expect("GIF width must fit in u16"),
+      u16::try_from(height).expect("GIF height must fit in u16"),
+    );
+
+    let (palette, lookup) = quantize(self.frames.iter().map(|f| &f.image), 255);
+    let mut palette_bytes = vec![0u8; 256 * 3];
+    for (entry, color) in palette_bytes.chunks_exact_mut(3).zip(&palette) {
+      entry.copy_from_slice(&color.map(to_u8));
+    }
+
+    let mut bytes = Vec::new();
+    {
+      let mut encoder =
+        gif::Encoder::new(&mut bytes, width, height, &palette_bytes).expect("Failed to encode GIF");
+      encoder
+        // GIF의 반복 횟수는 처음 재생 뒤에 더 반복하는 횟수입니다.
+        .set_repeat(match self.looping.plays() {
+          None => gif::Repeat::Infinite,
+          Some(n) => gif::Repeat::Finite(n - 1),
+        })
+        .expect("Failed to encode GIF");
+
+      for frame in self.frames.iter() {
+        let indices: Vec<u8> = frame
+          .image
+          .pixels
+          .iter()
+          .map(|p| {
+            if p[3] < 0.5 {
+              TRANSPARENT_INDEX
+            } else {
+              lookup[bin_index(p)]
+            }
+          })
+          .collect();
+
+        let gif_frame = gif::Frame {
+          width,
+          height,
+          delay: ((frame.delay_ms + 5) / 10).min(u16::MAX as u32) as u16,
+          // 투명한 픽셀 뒤로 이전 프레임이 보이지 않도록 배경으로 지웁니다.
+          dispose: gif::DisposalMethod::Background,
+          transparent: Some(TRANSPARENT_INDEX),
+          buffer: indices.into(),
+          ..Default::default()
+        };
+        encoder
+          .write_frame(&gif_frame)
+          .expect("Failed to encode GIF");
+      }
+    }
+    bytes
+  }
+
+  // RGBA 8비트 APNG, 지연 시간은 밀리초 단위 그대로 저장합니다.
+  pub fn encode_apng(&self) -> Vec<u8> {
+    let (width, height) = self.size();
+
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    // APNG의 num_plays는 전체 재생 횟수이고 0이면 무한 반복입니다.
+    let plays = self.looping.plays().map_or(0, u32::from);
+    encoder
+      .set_animated(self.frames.len() as u32, plays)
+      .expect("Failed to encode APNG");
+
+    let mut writer = encoder.write_header().expect("Failed to encode APNG");
+    for frame in self.frames.iter() {
+      let data: Vec<u8> = frame
+        .image
+        .pixels
+        .iter()
+        .flat_map(|p| p.map(to_u8))
+        .collect();
+      writer
+        .set_frame_delay(frame.delay_ms.min(u16::MAX as u32) as u16, 1000)
+        .expect("Failed to encode APNG");
+      writer
+        .write_image_data(&data)
+        .expect("Failed to encode APNG");
+    }
+    writer.finish().expect("Failed to encode APNG");
+    bytes
+  }
+
+  #[allow(dead_code)]
+  pub fn write_gif(&self, filename: &str) {
+    std::fs::write(filename, self.encode_gif()).expect("Failed to save GIF file");
+  }
+
+  #[allow(dead_code)]
+  pub fn write_apng(&self, filename: &str) {
+    std::fs::write(filename, self.encode_apng()).expect("Failed to save APNG file");
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use image::AnimationDecoder;
+  use std::io::Cursor;
+
+  fn frames() -> Vec<Image> {
+    // 빨간 사각형이 오른쪽으로 움직이는 프레임들
+    (0..4)
+      .map(|k| {
+        let mut image = Image::from_pixels(16, 8, vec![[0.0, 0.0, 1.0, 1.0]; 16 * 8]);
+        image.fill_rect(k * 3, 2, 4, 4, [1.0, 0.0, 0.0, 1.0]);
+        image
+      })
+      .collect()
+  }
+
+  #[test]
+  fn test_median_cut_keeps_few_colors_exact() {
+    let image = Image::test_pattern(32, 16);
+    let (palette, lookup) = quantize(std::iter::once(&image), 255);
+    assert!(palette.len() <= 255);
+
+    for pixel in image.pixels.iter().take(32) {
+      let color = palette[lookup[bin_index(pixel)] as usize];
+      assert_eq!(color.map(to_u8), [pixel[0], pixel[1], pixel[2]].map(to_u8));
+    }
+
+    // 색이 많으면 팔레트 크기로 줄어듭니다.
+    let gradient = Image::linear_gradient(256, 1, [0.0; 4], [1.0; 4]);
+    let (palette, _) = quantize(std::iter::once(&gradient), 8);
+    assert_eq!(palette.len(), 8);
+  }
+
+  #[test]
+  fn test_gif_round_trip() {
+    let animation = Animation::from_images(frames(), 100, Looping::Infinite);
+    let bytes = animation.encode_gif();
+
+    let decoder = image::codecs::gif::GifDecoder::new(Cursor::new(bytes)).unwrap();
+    let decoded = decoder.into_frames().collect_frames().unwrap();
+    assert_eq!(decoded.len(), 4);
+    assert_eq!(decoded[1].delay().numer_denom_ms(), (100, 1));
+
+    let buffer = decoded[2].buffer();
+    assert_eq!(buffer.get_pixel(7, 3).0, [255, 0, 0, 255]);
+    assert_eq!(buffer.get_pixel(0, 0).0, [0, 0, 255, 255]);
+  }
+
+  #[test]
+  fn test_looping_matches_between_formats() {
+    for (looping, expected) in [
+      (Looping::Infinite, None),
+      (Looping::Times(1), Some(1)),
+      (Looping::Times(3), Some(3)),
+    ] {
+      let animation = Animation::from_images(frames(), 100, looping);
+
+      let mut decoder = gif::DecodeOptions::new()
+        .read_info(Cursor::new(animation.encode_gif()))
+        .unwrap();
+      decoder.read_next_frame().unwrap();
+      let gif_plays = match decoder.repeat() {
+        gif::Repeat::Infinite => None,
+        gif::Repeat::Finite(n) => Some(n + 1),
+      };
+
+      let reader = png::Decoder::new(Cursor::new(animation.encode_apng()))
+        .read_info()
+        .unwrap();
+      let apng_plays = match reader.info().animation_control.unwrap().num_plays {
+        0 => None,
+        n => Some(n as u16),
+      };
+
+      assert_eq!(gif_plays, expected);
+      assert_eq!(apng_plays, expected);
+    }
+  }
+
+  #[test]
+  #[should_panic(expected = "Looping::Times must play at least once")]
+  fn test_looping_zero_times() {
+    Animation::from_images(frames(), 100, Looping::Times(0)).encode_apng();
+  }
+
+  #[test]
+  fn test_apng_frames_and_delays() {
+    let mut animation = Animation::new(Looping::Times(2));
+    for (k, image) in frames().into_iter().enumerate() {
+      animation.push(image, 40 + k as u32 * 10);
+    }
+    let bytes = animation.encode_apng();
+
+    let decoder = png::Decoder::new(Cursor::new(bytes));
+    let mut reader = decoder.read_info().unwrap();
+    let control = reader.info().animation_control.unwrap();
+    assert_eq!((control.num_frames, control.num_plays), (4, 2));
+
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    for k in 0..4 {
+      reader.next_frame(&mut buffer).unwrap();
+      let frame = reader.info().frame_control.unwrap();
+      assert_eq!((frame.delay_num, frame.delay_den), (40 + k * 10, 1000));
+    }
+  }
+}
@@ -0,0 +1,420 @@
+use super::{luminance, BorderMode, Image};
+
+// FXAA 3.11 (quality) 설정
+#[derive(Clone, Copy, Debug)]
+pub struct Fxaa {
+  // 주변 밝기 범위가 최대 밝기의 이 비율보다 작으면 가장자리가 아닙니다.
+  pub edge_threshold: f32,
+  // 어두운 영역에서 잡음이 처리되지 않도록 하는 최소 범위
+  pub edge_threshold_min: f32,
+  // 한 픽셀보다 얇은 디테일을 흐리는 정도 (0이면 끔)
+  pub subpixel: f32,
+  // 가장자리 끝을 찾는 최대 탐색 횟수
+  pub search_steps: u32,
+}
+
+impl Default for Fxaa {
+  fn default() -> Self {
+    Self {
+      edge_threshold: 0.166,
+      edge_threshold_min: 0.0833,
+      subpixel: 0.75,
+      search_steps: 12,
+    }
+  }
+}
+
+// SMAA 1x 설정
+#[derive(Clone, Copy, Debug)]
+pub struct Smaa {
+  // 가장자리로 판정할 밝기 차이
+  pub threshold: f32,
+  // 가장자리 끝을 찾는 최대 거리 (픽셀)
+  pub max_search_steps: u32,
+}
+
+impl Default for Smaa {
+  fn default() -> Self {
+    Self {
+      threshold: 0.1,
+      max_search_steps: 16,
+    }
+  }
+}
+
+// SMAA의 국소 대비 적응 계수, 더 강한 가장자리 옆의 약한 가장자리는 무시합니다.
+const LOCAL_CONTRAST_ADAPTATION: f32 = 2.0;
+
+// 변 위의 위치 k에서 재구성한 실루엣 선이 (양쪽, 음쪽)으로 픽셀을 덮는 면적
+// along(k)는 위치 k에 같은 변이 이어지는지, crossing(b)는 경계 b에서 꺾이는 방향(+-0.5)입니다.
+fn edge_coverage(
+  k: i32, max_steps: u32, along: impl Fn(i32) -> bool, crossing: impl Fn(i32) -> f32,
+) -> (f32, f32) {
+  let mut start = k;
+  while k - start < max_steps as i32 && along(start - 1) {
+    start -= 1;
+  }
+  let mut end = k;
+  while end - k < max_steps as i32 && along(end + 1) {
+    end += 1;
+  }
+
+  // 탐색 거리 안에서 끝나지 않은 쪽은 꺾이지 않은 것으로 봅니다.
+  let h_start = if along(start - 1) {
+    0.0
+  } else {
+    crossing(start)
+  };
+  let h_end = if along(end + 1) {
+    0.0
+  } else {
+    crossing(end + 1)
+  };
+
+  // 양 끝에서 변의 가운데로 향하는 두 직선 (Z 모양이면 한 직선이 됩니다)
+  let (l, r) = (start as f32, (end + 1) as f32);
+  let mid = (l + r) * 0.5;
+  let y = |x: f32| {
+    if x < mid {
+      h_start * (mid - x) / (mid - l)
+    } else {
+      h_end * (x - mid) / (r - mid)
+    }
+  };
+
+  let (a, b) = (k as f32, (k + 1) as f32);
+  let mut intervals = vec![(a, b)];
+  if a < mid && mid < b {
+    intervals = vec![(a, mid), (mid, b)];
+  }
+
+  let (mut positive, mut negative) = (0.0, 0.0);
+  for (x0, x1) in intervals {
+    let area = (y(x0) + y(x1)) * 0.5 * (x1 - x0);
+    if area > 0.0 {
+      positive += area;
+    } else {
+      negative -= area;
+    }
+  }
+  (positive, negative)
+}
+
+impl Image {
+  fn luma_buffer(&self) -> Vec<f32> {
+    self.pixels.iter().map(luminance).collect()
+  }
+
+  pub fn fxaa(&self, params: &Fxaa) -> Image {
+    let luma = self.luma_buffer();
+    let (w, h) = (self.width as i32, self.height as i32);
+    let l = |i: i32, j: i32| luma[(i.clamp(0, w - 1) + j.clamp(0, h - 1) * w) as usize];
+    let sample_luma = |x: f32, y: f32| luminance(&self.sample_bilinear(x, y, BorderMode::Clamp));
+
+    let mut output = self.clone();
+    for j in 0..h {
+      for i in 0..w {
+        let m = l(i, j);
+        let (n, s, e, west) = (l(i, j - 1), l(i, j + 1), l(i + 1, j), l(i - 1, j));
+        let max = m.max(n).max(s).max(e).max(west);
+        let min = m.min(n).min(s).min(e).min(west);
+        let range = max - min;
+        if range < params.edge_threshold_min.max(max * params.edge_threshold) {
+          continue;
+        }
+
+        let (nw, ne) = (l(i - 1, j - 1), l(i + 1, j - 1));
+        let (sw, se) = (l(i - 1, j + 1), l(i + 1, j + 1));
+
+        // 한 픽셀짜리 디테일일수록 주변 평균과 크게 다릅니다.
+        let average = (2.0 * (n + s + e + west) + nw + ne + sw + se) / 12.0;
+        let subpixel = ((average - m).abs() / range).clamp(0.0, 1.0);
+        let subpixel = (-2.0 * subpixel + 3.0) * subpixel * subpixel;
+        let subpixel_offset = subpixel * subpixel * params.subpixel;
+
+        let horizontal =
+          (n + s - 2.0 * m).abs() * 2.0 + (nw + sw - 2.0 * west).abs() + (ne + se - 2.0 * e).abs();
+        let vertical =
+          (west + e - 2.0 * m).abs() * 2.0 + (nw + ne - 2.0 * n).abs() + (sw + se - 2.0 * s).abs();
+        let is_horizontal = horizontal >= vertical;
+
+        // 가장자리에 수직인 방향에서 더 가파른 쪽을 고릅니다.
+        let (luma1, luma2) = if is_horizontal { (n, s) } else { (west, e) };
+        let (gradient1, gradient2) = (luma1 - m, luma2 - m);
+        let (step, local_average) = if gradient1.abs() >= gradient2.abs() {
+          (-1.0, 0.5 * (luma1 + m))
+        } else {
+          (1.0, 0.5 * (luma2 + m))
+        };
+        let gradient_scaled = 0.25 * gradient1.abs().max(gradient2.abs());
+
+        // 두 픽셀의 경계 위를 따라 양쪽으로 가장자리가 끝나는 곳을 찾습니다.
+        let (x, y) = (i as f32, j as f32);
+        let (origin, along) = if is_horizontal {
+          ((x, y + step * 0.5), (1.0, 0.0))
+        } else {
+          ((x + step * 0.5, y), (0.0, 1.0))
+        };
+        let end_luma = |t: f32| sample_luma(origin.0 + along.0 * t, origin.1 + along.1 * t);
+
+        let (mut t1, mut t2) = (-1.0, 1.0);
+        let (mut end1, mut end2) = (end_luma(t1) - local_average, end_luma(t2) - local_average);
+        for _ in 0..params.search_steps {
+          let done1 = end1.abs() >= gradient_scaled;
+          let done2 = end2.abs() >= gradient_scaled;
+          if done1 && done2 {
+            break;
+          }
+          if !done1 {
+            t1 -= 1.0;
+            end1 = end_luma(t1) - local_average;
+          }
+          if !done2 {
+            t2 += 1.0;
+            end2 = end_luma(t2) - local_average;
+          }
+        }
+
+        // 가까운 끝에서 멀수록 경계를 덜 옮깁니다.
+        let (distance1, distance2) = (-t1, t2);
+        let (distance, end) = if distance1 < distance2 {
+          (distance1, end1)
+        } else {
+          (distance2, end2)
+        };
+        let edge_offset = 0.5 - distance / (distance1 + distance2);
+
+        // 끝의 밝기 변화가 가운데와 같은 방향이어야 실루엣이 이 픽셀을 지나갑니다.
+        let edge_offset = if (end < 0.0) != (m < local_average) {
+          edge_offset
+        } else {
+          0.0
+        };
+
+        let offset = edge_offset.max(subpixel_offset) * step;
+        output.pixels[(i + j * w) as usize] = if is_horizontal {
+          self.sample_bilinear(x, y + offset, BorderMode::Clamp)
+        } else {
+          self.sample_bilinear(x + offset, y, BorderMode::Clamp)
+        };
+      }
+    }
+    output
+  }
+
+  // SMAA 1단계: R은 왼쪽 픽셀과의 경계, G는 위쪽 픽셀과의 경계 (0 또는 1)
+  pub fn smaa_edges(&self, threshold: f32) -> Image {
+    let luma = self.luma_buffer();
+    let (w, h) = (self.width as i32, self.height as i32);
+    let l = |i: i32, j: i32| luma[(i.clamp(0, w - 1) + j.clamp(0, h - 1) * w) as usize];
+
+    let mut edges = Image::new(self.width, self.height);
+    for j in 0..h {
+      for i in 0..w {
+        let m = l(i, j);
+        let delta_left = if i > 0 { (m - l(i - 1, j)).abs() } else { 0.0 };
+        let delta_top = if j > 0 { (m - l(i, j - 1)).abs() } else { 0.0 };
+        if delta_left < threshold && delta_top < threshold {
+          continue;
+        }
+
+        let max_delta = [
+          delta_left,
+          delta_top,
+          (m - l(i + 1, j)).abs(),
+          (m - l(i, j + 1)).abs(),
+          (l(i - 1, j) - l(i - 2, j)).abs(),
+          (l(i, j - 1) - l(i, j - 2)).abs(),
+        ]
+        .into_iter()
+        .fold(0.0, f32::max);
+
+        let edge =
+          |delta: f32| delta >= threshold && LOCAL_CONTRAST_ADAPTATION * delta >= max_delta;
+        let pixel = &mut edges.pixels[(i + j * w) as usize];
+        pixel[0] = if edge(delta_left) { 1.0 } else { 0.0 };
+        pixel[1] = if edge(delta_top) { 1.0 } else { 0.0 };
+      }
+    }
+    edges
+  }
+
+  // SMAA 2단계: smaa_edges 결과에서 각 픽셀이 (위, 아래, 왼쪽, 오른쪽) 이웃을 섞을 비율
+  // 원래 SMAA의 면적 텍스처 대신 양 끝의 꺾임으로 재구성한 직선의 면적을 직접 계산합니다.
+  pub fn smaa_blend_weights(&self, max_search_steps: u32) -> Image {
+    let (w, h) = (self.width as i32, self.height as i32);
+    let inside = |i: i32, j: i32| i >= 0 && i < w && j >= 0 && j < h;
+    let left = |i: i32, j: i32| inside(i, j) && self.pixels[(i + j * w) as usize][0] > 0.5;
+    let top = |i: i32, j: i32| inside(i, j) && self.pixels[(i + j * w) as usize][1] > 0.5;
+    let bend = |positive: bool, negative: bool| match (positive, negative) {
+      (true, false) => 0.5,
+      (false, true) => -0.5,
+      _ => 0.0,
+    };
+
+    let mut weights = Image::new(self.width, self.height);
+    for j in 0..h {
+      for i in 0..w {
+        // 위쪽 경계: 양쪽은 아래 픽셀 (i, j), 음쪽은 위 픽셀 (i, j - 1)
+        if top(i, j) {
+          let (below, above) = edge_coverage(
+            i,
+            max_search_steps,
+            |k| top(k, j),
+            |b| bend(left(b, j), left(b, j - 1)),
+          );
+          weights.pixels[(i + j * w) as usize][0] = below;
+          if j > 0 {
+            weights.pixels[(i + (j - 1) * w) as usize][1] = above;
+          }
+        }
+
+        // 왼쪽 경계: 양쪽은 오른쪽 픽셀 (i, j), 음쪽은 왼쪽 픽셀 (i - 1, j)
+        if left(i, j) {
+          let (right, left_side) = edge_coverage(
+            j,
+            max_search_steps,
+            |k| left(i, k),
+            |b| bend(top(i, b), top(i - 1, b)),
+          );
+          weights.pixels[(i + j * w) as usize][2] = right;
+          if i > 0 {
+            weights.pixels[(i - 1 + j * w) as usize][3] = left_side;
+          }
+        }
+      }
+    }
+    weights
+  }
+
+  // SMAA 3단계: 가중치만큼 이웃 픽셀과 섞습니다.
+  pub fn smaa_neighborhood_blending(&self, weights: &Image) -> Image {
+    assert!(
+      self.width == weights.width && self.height == weights.height,
+      "Image size mismatch"
+    );
+
+    let mut output = self.clone();
+    for j in 0..self.height as i32 {
+      for i in 0..self.width as i32 {
+        let weight = weights.get_pixel(i, j);
+        let total: f32 = weight.iter().sum();
+        if total <= 0.0 {
+          continue;
+        }
+
+        let scale = if total > 1.0 { 1.0 / total } else { 1.0 };
+        let neighbors = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+        let mut color = self.get_pixel(i, j).map(|v| v * (1.0 - total * scale));
+        for (&(di, dj), &w) in neighbors.iter().zip(weight) {
+          let neighbor = self.get_pixel(i + di, j + dj);
+          for c in 0..4 {
+            color[c] += neighbor[c] * w * scale;
+          }
+        }
+        *output.get_pixel_mut(i, j) = color;
+      }
+    }
+    output
+  }
+
+  pub fn smaa(&self, params: &Smaa) -> Image {
+    let weights = self
+      .smaa_edges(params.threshold)
+      .smaa_blend_weights(params.max_search_steps);
+    self.smaa_neighborhood_blending(&weights)
+  }
+
+  // factor배로 렌더링한 이미지를 상자 필터로 줄여서 안티에일리어싱 기준 영상을 만듭니다.
+  pub fn resolve_supersampled(&self, factor: u32) -> Image {
+    assert!(
+      factor > 0 && self.width.is_multiple_of(factor) && self.height.is_multiple_of(factor),
+      "Image size must be a multiple of the supersampling factor"
+    );
+
+    let mut output = Image::new(self.width / factor, self.height / factor);
+    for j in 0..output.height {
+      for i in 0..output.width {
+        output.pixels[(i + j * output.width) as usize] =
+          self.view(i * factor, j * factor, factor, factor).mean();
+      }
+    }
+    output
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::image::draw::FillRule;
+
+  // scale배 해상도로 그린 삼각형, 하위 샘플들이 원래 픽셀 안에 고르게 놓이도록 옮깁니다.
+  fn render_triangle(scale: u32) -> Image {
+    let s = scale as f32;
+    let offset = (s - 1.0) * 0.5;
+    let points: Vec<(f32, f32)> = [(2.0, 4.0), (30.0, 9.0), (8.0, 29.0)]
+      .iter()
+      .map(|&(x, y)| (x * s + offset, y * s + offset))
+      .collect();
+
+    let mut image = Image::new(32 * scale, 32 * scale);
+    image.as_view_mut().fill([0.0, 0.0, 0.0, 1.0]);
+    image.fill_polygon(&points, [1.0; 4], FillRule::NonZero);
+    image
+  }
+
+  #[test]
+  fn test_smaa_edges_and_straight_lines() {
+    let mut image = Image::new(8, 8);
+    image.fill_rect(3, 0, 5, 8, [1.0; 4]);
+
+    let edges = image.smaa_edges(0.1);
+    assert_eq!(edges.get_pixel(3, 4)[0], 1.0);
+    assert_eq!(edges.get_pixel(4, 4)[0], 0.0);
+    assert!(edges.pixels.iter().all(|p| p[1] == 0.0));
+
+    // 꺾이지 않는 직선 가장자리는 그대로 둡니다.
+    assert_eq!(image.smaa(&Smaa::default()).pixels, image.pixels);
+  }
+
+  #[test]
+  fn test_smaa_blends_staircase() {
+    // 한 칸씩 내려가는 계단은 양쪽 픽셀에 섞입니다.
+    let mut image = Image::new(8, 8);
+    image.fill_rect(0, 4, 4, 4, [1.0; 4]);
+    image.fill_rect(4, 5, 4, 3, [1.0; 4]);
+
+    let weights = image.smaa_edges(0.1).smaa_blend_weights(16);
+    // 계단의 안쪽 모서리 양 옆으로 위쪽 색이 아래로, 아래쪽 색이 위로 번집니다.
+    assert!(weights.get_pixel(3, 4)[0] > 0.0 && weights.get_pixel(3, 3)[1] == 0.0);
+    assert!(weights.get_pixel(4, 4)[1] > 0.0 && weights.get_pixel(4, 5)[0] == 0.0);
+
+    let blended = image.smaa(&Smaa::default());
+    let v = blended.get_pixel(4, 4)[0];
+    assert!(v > 0.0 && v < 1.0);
+  }
+
+  #[test]
+  fn test_smaa_blend_weights_on_image_border() {
+    // 직접 만든 경계 이미지는 첫 행과 첫 열에도 플래그가 있을 수 있습니다.
+    let mut edges = Image::new(8, 8);
+    edges.fill_rect(0, 0, 8, 1, [1.0, 1.0, 0.0, 1.0]);
+    edges.fill_rect(0, 0, 1, 8, [1.0, 1.0, 0.0, 1.0]);
+
+    let weights = edges.smaa_blend_weights(16);
+    assert_eq!((weights.width, weights.height), (8, 8));
+    assert!(weights.pixels.iter().flatten().all(|v| v.is_finite()));
+  }
+
+  #[test]
+  fn test_antialiasing_approaches_supersampled_reference() {
+    let reference = render_triangle(4).resolve_supersampled(4);
+    let aliased = render_triangle(1);
+
+    let base = aliased.psnr(&reference, 3);
+    let fxaa = aliased.fxaa(&Fxaa::default()).psnr(&reference, 3);
+    let smaa = aliased.smaa(&Smaa::default()).psnr(&reference, 3);
+    assert!(fxaa > base + 1.0, "FXAA {} vs {}", fxaa, base);
+    assert!(smaa > base + 1.0, "SMAA {} vs {}", smaa, base);
+  }
+}
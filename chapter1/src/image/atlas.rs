@@ -0,0 +1,522 @@
+use super::Image;
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PackingAlgorithm {
+  // 남은 빈 사각형 목록에서 짧은 변의 여유가 가장 작은 곳에 배치 (Best Short Side Fit)
+  MaxRects,
+  // 위쪽 윤곽선(skyline)에서 가장 낮게 놓이는 곳에 배치 (Bottom Left), 더 빠르지만 빈틈이 남습니다.
+  Skyline,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct AtlasOptions {
+  // 페이지 하나의 크기, 다 들어가지 않으면 페이지를 더 만듭니다.
+  pub width: u32,
+  pub height: u32,
+  // 이웃한 이미지(와 페이지 가장자리) 사이의 빈 픽셀 수
+  pub padding: u32,
+  // 쌍선형 샘플링이 이웃 이미지를 섞지 않도록 가장자리 픽셀을 바깥으로 복사할 두께
+  pub extrude: u32,
+  pub algorithm: PackingAlgorithm,
+}
+
+impl Default for AtlasOptions {
+  fn default() -> Self {
+    Self {
+      width: 1024,
+      height: 1024,
+      padding: 2,
+      extrude: 1,
+      algorithm: PackingAlgorithm::MaxRects,
+    }
+  }
+}
+
+// 페이지 안에서 원본 이미지가 놓인 위치 (돌출된 가장자리는 제외)
+#[derive(Clone, Debug, PartialEq)]
+pub struct AtlasEntry {
+  pub name: String,
+  pub page: usize,
+  pub x: u32,
+  pub y: u32,
+  pub width: u32,
+  pub height: u32,
+  // (u0, v0, u1, v1), v는 아래 방향 (wgpu 텍스처 좌표)
+  pub uv: [f32; 4],
+}
+
+pub struct Atlas {
+  pub pages: Vec<Image>,
+  pub entries: Vec<AtlasEntry>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Rect {
+  x: u32,
+  y: u32,
+  width: u32,
+  height: u32,
+}
+
+impl Rect {
+  fn right(&self) -> u32 {
+    self.x + self.width
+  }
+
+  fn bottom(&self) -> u32 {
+    self.y + self.height
+  }
+
+  fn intersects(&self, other: &Rect) -> bool {
+    self.x < other.right()
+      && other.x < self.right()
+      && self.y < other.bottom()
+      && other.y < self.bottom()
+  }
+
+  fn contains(&self, other: &Rect) -> bool {
+    other.x >= self.x
+      && other.y >= self.y
+      && other.right() <= self.right()
+      && other.bottom() <= self.bottom()
+  }
+}
+
+// 페이지 하나에 사각형을 배치하는 방식
+enum Packer {
+  MaxRects {
+    free: Vec<Rect>,
+  },
+  // (x, y, 너비) 구간들, x 순서로 정렬되어 있습니다.
+  Skyline {
+    width: u32,
+    height: u32,
+    nodes: Vec<(u32, u32, u32)>,
+  },
+}
+
+impl Packer {
+  fn new(algorithm: PackingAlgorithm, width: u32, height: u32) -> Self {
+    match algorithm {
+      PackingAlgorithm::MaxRects => Packer::MaxRects {
+        free: vec![Rect {
+          x: 0,
+          y: 0,
+          width,
+          height,
+        }],
+      },
+      PackingAlgorithm::Skyline => Packer::Skyline {
+        width,
+        height,
+        nodes: vec![(0, 0, width)],
+      },
+    }
+  }
+
+  fn insert(&mut self, width: u32, height: u32) -> Option<(u32, u32)> {
+    match self {
+      Packer::MaxRects { free } => {
+        let placed = free
+          .iter()
+          .filter(|f| f.width >= width && f.height >= height)
+          .min_by_key(|f| {
+            let (dw, dh) = (f.width - width, f.height - height);
+            (dw.min(dh), dw.max(dh))
+          })
+          .map(|f| Rect {
+            x: f.x,
+            y: f.y,
+            width,
+            height,
+          })?;
+
+        // 배치한 사각형과 겹치는 빈 사각형을 최대 네 개의 사각형으로 나눕니다.
+        let mut next = Vec::with_capacity(free.len() + 4);
+        for f in free.iter() {
+          if !f.intersects(&placed) {
+            next.push(*f);
+            continue;
+          }
+          if placed.x > f.x {
+            next.push(Rect {
+              width: placed.x - f.x,
+              ..*f
+            });
+          }
+          if placed.right() < f.right() {
+            next.push(Rect {
+              x: placed.right(),
+              width: f.right() - placed.right(),
+              ..*f
+            });
+          }
+          if placed.y > f.y {
+            next.push(Rect {
+              height: placed.y - f.y,
+              ..*f
+            });
+          }
+          if placed.bottom() < f.bottom() {
+            next.push(Rect {
+              y: placed.bottom(),
+              height: f.bottom() - placed.bottom(),
+              ..*f
+            });
+          }
+        }
+
+        // 다른 빈 사각형에 포함되는 사각형 제거
+        let mut pruned: Vec<Rect> = Vec::with_capacity(next.len());
+        for (k, r) in next.iter().enumerate() {
+          let redundant = next
+            .iter()
+            .enumerate()
+            .any(|(m, other)| m != k && other.contains(r) && (other != r || m < k));
+          if !redundant {
+            pruned.push(*r);
+          }
+        }
+        *free = pruned;
+
+        Some((placed.x, placed.y))
+      }
+      Packer::Skyline {
+        width: page_width,
+        height: page_height,
+        nodes,
+      } => {
+        // (윗변 y, x, 시작 노드)
+        let mut best: Option<(u32, u32, usize)> = None;
+        for start in 0..nodes.len() {
+          let x = nodes[start].0;
+          if x + width > *page_width {
+            break;
+          }
+
+          // x..x + width 구간에 걸친 노드 중 가장 높은 곳에 놓입니다.
+          let mut y = 0;
+          let mut covered = 0;
+          for node in nodes[start..].iter() {
+            y = y.max(node.1);
+            covered += node.2;
+            if covered >= width {
+              break;
+            }
+          }
+          if y + height > *page_height {
+            continue;
+          }
+          if best.is_none_or(|(best_y, best_x, _)| (y + height, x) < (best_y, best_x)) {
+            best = Some((y + height, x, start));
+          }
+        }
+
+        let (top, x, start) = best?;
+        let y = top - height;
+
+        // 새 노드를 넣고 그 아래에 가려진 노드를 잘라냅니다.
+        nodes.insert(start, (x, top, width));
+        let end = x + width;
+        let mut k = start + 1;
+        while k < nodes.len() && nodes[k].0 < end {
+          let (nx, ny, nw) = nodes[k];
+          if nx + nw <= end {
+            nodes.remove(k);
+          } else {
+            nodes[k] = (end, ny, nx + nw - end);
+            k += 1;
+          }
+        }
+
+        // 높이가 같은 이웃 노드 합치기
+        let mut k = 0;
+        while k + 1 < nodes.len() {
+          if nodes[k].1 == nodes[k + 1].1 {
+            nodes[k].2 += nodes[k + 1].2;
+            nodes.remove(k + 1);
+          } else {
+            k += 1;
+          }
+        }
+
+        Some((x, y))
+      }
+    }
+  }
+}
+
+fn escape_json(text: &str) -> String {
+  let mut escaped = String::with_capacity(text.len() + 2);
+  for c in text.chars() {
+    match c {
+      '"' => escaped.push_str("\\\""),
+      '\\' => escaped.push_str("\\\\"),
+      '\n' => escaped.push_str("\\n"),
+      '\r' => escaped.push_str("\\r"),
+      '\t' => escaped.push_str("\\t"),
+      c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+      c => escaped.push(c),
+    }
+  }
+  escaped
+}
+
+// 이미지들을 한 장 이상의 아틀라스 페이지에 배치합니다.
+// 큰 이미지부터 배치하고, entries는 입력과 같은 순서입니다.
+pub fn pack_atlas(images: &[(&str, &Image)], options: &AtlasOptions) -> Atlas {
+  let (padding, extrude) = (options.padding, options.extrude);
+  // 왼쪽과 위쪽 가장자리의 padding은 사용 영역을 줄여서 확보합니다.
+  let usable_width = options.width.saturating_sub(padding);
+  let usable_height = options.height.saturating_sub(padding);
+  let cell = |image: &Image| {
+    (
+      image.width + 2 * extrude + padding,
+      image.height + 2 * extrude + padding,
+    )
+  };
+
+  let mut order: Vec<usize> = (0..images.len()).collect();
+  order.sort_by_key(|&k| {
+    let (w, h) = cell(images[k].1);
+    std::cmp::Reverse((w.max(h), w * h))
+  });
+
+  let mut packers: Vec<Packer> = Vec::new();
+  let mut placements = vec![(0, 0, 0); images.len()];
+  for k in order {
+    let (name, image) = images[k];
+    let (w, h) = cell(image);
+    assert!(
+      w <= usable_width && h <= usable_height,
+      "Image {:?} does not fit in an atlas page",
+      name
+    );
+
+    let placed = packers
+      .iter_mut()
+      .enumerate()
+      .find_map(|(page, packer)| packer.insert(w, h).map(|(x, y)| (page, x, y)));
+    placements[k] = placed.unwrap_or_else(|| {
+      let mut packer = Packer::new(options.algorithm, usable_width, usable_height);
+      let (x, y) = packer.insert(w, h).unwrap();
+      packers.push(packer);
+      (packers.len() - 1, x, y)
+    });
+  }
+
+  let mut pages: Vec<Image> = (0..packers.len())
+    .map(|_| Image::new(options.width, options.height))
+    .collect();
+  let mut entries = Vec::with_capacity(images.len());
+
+  for (&(name, image), &(page, cell_x, cell_y)) in images.iter().zip(&placements) {
+    let (x, y) = (cell_x + padding + extrude, cell_y + padding + extrude);
+    let target = &mut pages[page];
+
+    // get_pixel은 범위 밖을 가장자리로 고정하므로 돌출 영역이 가장자리 픽셀로 채워집니다.
+    let e = extrude as i32;
+    for j in -e..image.height as i32 + e {
+      for i in -e..image.width as i32 + e {
+        *target.get_pixel_mut(x as i32 + i, y as i32 + j) = *image.get_pixel(i, j);
+      }
+    }
+
+    let (page_width, page_height) = (options.width as f32, options.height as f32);
+    entries.push(AtlasEntry {
+      name: name.to_string(),
+      page,
+      x,
+      y,
+      width: image.width,
+      height: image.height,
+      uv: [
+        x as f32 / page_width,
+        y as f32 / page_height,
+        (x + image.width) as f32 / page_width,
+        (y + image.height) as f32 / page_height,
+      ],
+    });
+  }
+
+  Atlas { pages, entries }
+}
+
+impl Atlas {
+  // page_files[k]는 k번째 페이지의 파일 이름입니다.
+  pub fn manifest_json(&self, page_files: &[String]) -> String {
+    assert_eq!(
+      page_files.len(),
+      self.pages.len(),
+      "Page file count mismatch"
+    );
+
+    let pages: Vec<String> = self
+      .pages
+      .iter()
+      .zip(page_files)
+      .map(|(page, file)| {
+        format!(
+          "    {{ \"file\": \"{}\", \"width\": {}, \"height\": {} }}",
+          escape_json(file),
+          page.width,
+          page.height
+        )
+      })
+      .collect();
+
+    let sprites: Vec<String> = self
+      .entries
+      .iter()
+      .map(|e| {
+        format!(
+          "    {{ \"name\": \"{}\", \"page\": {}, \"x\": {}, \"y\": {}, \"width\": {}, \"height\": {}, \"uv\": [{}, {}, {}, {}] }}",
+          escape_json(&e.name),
+          e.page,
+          e.x,
+          e.y,
+          e.width,
+          e.height,
+          e.uv[0],
+          e.uv[1],
+          e.uv[2],
+          e.uv[3]
+        )
+      })
+      .collect();
+
+    format!(
+      "{{\n  \"pages\": [\n{}\n  ],\n  \"sprites\": [\n{}\n  ]\n}}\n",
+      pages.join(",\n"),
+      sprites.join(",\n")
+    )
+  }
+
+  // prefix_0.png, prefix_1.png, ... 와 prefix.json을 저장합니다.
+  #[allow(dead_code)]
+  pub fn write(&self, prefix: &str) {
+    let files: Vec<String> = (0..self.pages.len())
+      .map(|k| format!("{}_{}.png", prefix, k))
+      .collect();
+    for (page, file) in self.pages.iter().zip(&files) {
+      page.write_png(file);
+    }
+
+    // 매니페스트에는 JSON 파일 기준의 상대 경로를 기록합니다.
+    let names: Vec<String> = files
+      .iter()
+      .map(|f| {
+        Path::new(f)
+          .file_name()
+          .unwrap()
+          .to_string_lossy()
+          .into_owned()
+      })
+      .collect();
+    std::fs::write(format!("{}.json", prefix), self.manifest_json(&names))
+      .expect("Failed to save atlas manifest");
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::image::noise::Rng;
+
+  fn sprites(count: usize) -> Vec<(String, Image)> {
+    let mut rng = Rng::new(11);
+    (0..count)
+      .map(|k| {
+        let (w, h) = (4 + rng.below(20), 4 + rng.below(20));
+        let mut image = Image::new(w, h);
+        image
+          .as_view_mut()
+          .fill([k as f32 / count as f32, 0.5, 1.0, 1.0]);
+        (format!("sprite_{}", k), image)
+      })
+      .collect()
+  }
+
+  fn cells(atlas: &Atlas, options: &AtlasOptions) -> Vec<(usize, Rect)> {
+    // 돌출 영역과 padding을 포함한 영역
+    let margin = options.extrude + options.padding;
+    atlas
+      .entries
+      .iter()
+      .map(|e| {
+        (
+          e.page,
+          Rect {
+            x: e.x - margin,
+            y: e.y - margin,
+            width: e.width + 2 * options.extrude + options.padding,
+            height: e.height + 2 * options.extrude + options.padding,
+          },
+        )
+      })
+      .collect()
+  }
+
+  #[test]
+  fn test_packing_has_no_overlaps() {
+    let sprites = sprites(40);
+    let images: Vec<(&str, &Image)> = sprites.iter().map(|(n, i)| (n.as_str(), i)).collect();
+
+    for algorithm in [PackingAlgorithm::MaxRects, PackingAlgorithm::Skyline] {
+      let options = AtlasOptions {
+        width: 96,
+        height: 96,
+        padding: 2,
+        extrude: 1,
+        algorithm,
+      };
+      let atlas = pack_atlas(&images, &options);
+      assert!(atlas.pages.len() > 1);
+      assert_eq!(atlas.entries.len(), 40);
+
+      let cells = cells(&atlas, &options);
+      for (k, (page, a)) in cells.iter().enumerate() {
+        assert!(a.right() <= 96 - options.padding && a.bottom() <= 96 - options.padding);
+        for (other_page, b) in cells[k + 1..].iter() {
+          assert!(page != other_page || !a.intersects(b), "{:?} {:?}", a, b);
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn test_extrusion_and_uvs() {
+    let mut image = Image::new(3, 2);
+    image.fill_rect(0, 0, 1, 2, [1.0, 0.0, 0.0, 1.0]);
+    image.fill_rect(2, 0, 1, 2, [0.0, 0.0, 1.0, 1.0]);
+
+    let options = AtlasOptions {
+      width: 16,
+      height: 8,
+      padding: 1,
+      extrude: 2,
+      algorithm: PackingAlgorithm::Skyline,
+    };
+    let atlas = pack_atlas(&[("quad", &image)], &options);
+    let entry = &atlas.entries[0];
+    assert_eq!((entry.x, entry.y), (3, 3));
+    assert_eq!(entry.uv, [3.0 / 16.0, 3.0 / 8.0, 6.0 / 16.0, 5.0 / 8.0]);
+
+    let page = &atlas.pages[0];
+    assert_eq!(page.get_pixel(1, 1), &[1.0, 0.0, 0.0, 1.0]);
+    assert_eq!(page.get_pixel(7, 6), &[0.0, 0.0, 1.0, 1.0]);
+    assert_eq!(page.get_pixel(0, 0), &[0.0; 4]);
+  }
+
+  #[test]
+  fn test_manifest_json() {
+    let image = Image::new(2, 2);
+    let atlas = pack_atlas(&[("a \"quoted\" name", &image)], &AtlasOptions::default());
+    let json = atlas.manifest_json(&["atlas_0.png".to_string()]);
+
+    assert!(json.contains("\"file\": \"atlas_0.png\""));
+    assert!(json.contains("\"name\": \"a \\\"quoted\\\" name\""));
+    assert!(json.contains("\"uv\": [0.0029296875, 0.0029296875, 0.0048828125, 0.0048828125]"));
+  }
+}
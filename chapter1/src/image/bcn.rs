@@ -0,0 +1,756 @@
+use super::{to_u8, Image};
+use glam::{Mat4, Vec4};
+
+// wgpu::TextureFormat::Bc* 와 같은 블록 압축 형식 (4x4 픽셀 블록)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BcFormat {
+  // RGB 565 + 1비트 알파
+  Bc1,
+  // BC4 알파 블록 + BC1 색 블록
+  Bc3,
+  // 빨강 단일 채널
+  Bc4,
+  // 빨강, 초록 두 채널 (노멀 맵)
+  Bc5,
+  // RGBA, 모드 6만 사용합니다.
+  Bc7,
+}
+
+// 인코딩 품질과 속도의 균형
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BcQuality {
+  // 경계 상자의 양 끝을 끝점으로 사용
+  Fast,
+  // 주성분 축으로 끝점을 고르고 최소 제곱법으로 다듬기
+  High,
+}
+
+pub struct CompressedImage {
+  pub format: BcFormat,
+  pub width: u32,
+  pub height: u32,
+  pub data: Vec<u8>,
+}
+
+impl BcFormat {
+  pub fn block_size(&self) -> usize {
+    match self {
+      BcFormat::Bc1 | BcFormat::Bc4 => 8,
+      BcFormat::Bc3 | BcFormat::Bc5 | BcFormat::Bc7 => 16,
+    }
+  }
+
+  // 압축 결과를 비교할 채널 수
+  pub fn channels(&self) -> usize {
+    match self {
+      BcFormat::Bc4 => 1,
+      BcFormat::Bc5 => 2,
+      _ => 4,
+    }
+  }
+
+  // BC4, BC5에는 sRGB 형식이 없으므로 srgb를 무시합니다.
+  pub fn texture_format(&self, srgb: bool) -> wgpu::TextureFormat {
+    use wgpu::TextureFormat as F;
+    match (self, srgb) {
+      (BcFormat::Bc1, false) => F::Bc1RgbaUnorm,
+      (BcFormat::Bc1, true) => F::Bc1RgbaUnormSrgb,
+      (BcFormat::Bc3, false) => F::Bc3RgbaUnorm,
+      (BcFormat::Bc3, true) => F::Bc3RgbaUnormSrgb,
+      (BcFormat::Bc4, _) => F::Bc4RUnorm,
+      (BcFormat::Bc5, _) => F::Bc5RgUnorm,
+      (BcFormat::Bc7, false) => F::Bc7RgbaUnorm,
+      (BcFormat::Bc7, true) => F::Bc7RgbaUnormSrgb,
+    }
+  }
+}
+
+// 블록 안의 픽셀마다 끝점 e0에서 e1 쪽으로의 보간 비율 (None은 끝점 맞추기에서 제외)
+struct Fit<B> {
+  block: B,
+  error: f32,
+  weights: [Option<f32>; 16],
+}
+
+fn bounding_box(points: &[Vec4; 16], mask: &[bool; 16]) -> (Vec4, Vec4) {
+  let mut selected = points.iter().zip(mask).filter(|(_, &m)| m).map(|(p, _)| *p);
+  let Some(first) = selected.next() else {
+    return (Vec4::ZERO, Vec4::ZERO);
+  };
+  selected.fold((first, first), |(min, max), p| (min.min(p), max.max(p)))
+}
+
+// 공분산 행렬의 주성분 축 위로 투영했을 때의 양 끝점
+fn principal_axis(points: &[Vec4; 16], mask: &[bool; 16]) -> (Vec4, Vec4) {
+  let selected: Vec<Vec4> = points
+    .iter()
+    .zip(mask)
+    .filter(|(_, &m)| m)
+    .map(|(p, _)| *p)
+    .collect();
+  if selected.is_empty() {
+    return (Vec4::ZERO, Vec4::ZERO);
+  }
+
+  let mean = selected.iter().sum::<Vec4>() / selected.len() as f32;
+  let mut covariance = Mat4::ZERO;
+  for p in selected.iter() {
+    let d = *p - mean;
+    covariance.x_axis += d * d.x;
+    covariance.y_axis += d * d.y;
+    covariance.z_axis += d * d.z;
+    covariance.w_axis += d * d.w;
+  }
+
+  // 거듭제곱법(power iteration)
+  let (min, max) = bounding_box(points, mask);
+  let mut axis = max - min;
+  for _ in 0..8 {
+    let next = covariance * axis;
+    if next.length_squared() < 1e-12 {
+      break;
+    }
+    axis = next.normalize();
+  }
+  if axis.length_squared() < 1e-12 {
+    return (mean, mean);
+  }
+  let axis = axis.normalize();
+
+  let (t_min, t_max) = selected.iter().fold((f32::MAX, f32::MIN), |(lo, hi), p| {
+    let t = (*p - mean).dot(axis);
+    (lo.min(t), hi.max(t))
+  });
+  (mean + axis * t_min, mean + axis * t_max)
+}
+
+// 현재 보간 비율을 고정하고 Σ |(1 - t) e0 + t e1 - p|² 를 최소로 하는 끝점
+fn least_squares(points: &[Vec4; 16], weights: &[Option<f32>; 16]) -> Option<(Vec4, Vec4)> {
+  let (mut a, mut b, mut c) = (0.0, 0.0, 0.0);
+  let (mut rhs0, mut rhs1) = (Vec4::ZERO, Vec4::ZERO);
+  for (p, t) in points.iter().zip(weights) {
+    let Some(t) = *t else {
+      continue;
+    };
+    let s = 1.0 - t;
+    a += s * s;
+    b += s * t;
+    c += t * t;
+    rhs0 += *p * s;
+    rhs1 += *p * t;
+  }
+
+  let det = a * c - b * b;
+  if det.abs() < 1e-6 {
+    return None;
+  }
+  let e0 = (rhs0 * c - rhs1 * b) / det;
+  let e1 = (rhs1 * a - rhs0 * b) / det;
+  Some((
+    e0.clamp(Vec4::ZERO, Vec4::ONE),
+    e1.clamp(Vec4::ZERO, Vec4::ONE),
+  ))
+}
+
+fn fit_endpoints<B>(
+  points: &[Vec4; 16], mask: &[bool; 16], quality: BcQuality, encode: impl Fn(Vec4, Vec4) -> Fit<B>,
+) -> Fit<B> {
+  let (e0, e1) = match quality {
+    BcQuality::Fast => bounding_box(points, mask),
+    BcQuality::High => principal_axis(points, mask),
+  };
+
+  let mut best = encode(e0, e1);
+  if quality == BcQuality::High {
+    for _ in 0..4 {
+      let Some((e0, e1)) = least_squares(points, &best.weights) else {
+        break;
+      };
+      let candidate = encode(e0, e1);
+      if candidate.error >= best.error {
+        break;
+      }
+      best = candidate;
+    }
+  }
+  best
+}
+
+// 팔레트에서 가장 가까운 색의 인덱스와 오차
+fn nearest(point: Vec4, palette: &[Vec4]) -> (usize, f32) {
+  palette
+    .iter()
+    .map(|c| (*c - point).length_squared())
+    .enumerate()
+    .min_by(|a, b| a.1.total_cmp(&b.1))
+    .unwrap()
+}
+
+// BC1 색 블록
+
+fn to_565(color: Vec4) -> u16 {
+  let c = color.clamp(Vec4::ZERO, Vec4::ONE);
+  let (r, g, b) = (
+    (c.x * 31.0).round() as u16,
+    (c.y * 63.0).round() as u16,
+    (c.z * 31.0).round() as u16,
+  );
+  (r << 11) | (g << 5) | b
+}
+
+fn from_565(value: u16) -> [u32; 3] {
+  let (r, g, b) = (
+    (value >> 11) as u32 & 31,
+    (value >> 5) as u32 & 63,
+    value as u32 & 31,
+  );
+  [
+    (r << 3) | (r >> 2),
+    (g << 2) | (g >> 4),
+    (b << 3) | (b >> 2),
+  ]
+}
+
+// 4색 모드: c0, c1, (2c0 + c1) / 3, (c0 + 2c1) / 3
+// 3색 모드(c0 <= c1): c0, c1, (c0 + c1) / 2, 투명한 검정
+fn bc1_palette(c0: u16, c1: u16, four_color: bool) -> [Vec4; 4] {
+  let (a, b) = (from_565(c0), from_565(c1));
+  let mix = |wa: u32, wb: u32| {
+    let v: [f32; 3] = std::array::from_fn(|k| ((wa * a[k] + wb * b[k]) / (wa + wb)) as f32 / 255.0);
+    Vec4::new(v[0], v[1], v[2], 1.0)
+  };
+
+  if four_color {
+    [mix(1, 0), mix(0, 1), mix(2, 1), mix(1, 2)]
+  } else {
+    [mix(1, 0), mix(0, 1), mix(1, 1), Vec4::ZERO]
+  }
+}
+
+fn encode_bc1(block: &[[f32; 4]; 16], quality: BcQuality, four_color_only: bool) -> [u8; 8] {
+  let transparent: [bool; 16] = std::array::from_fn(|k| !four_color_only && block[k][3] < 0.5);
+  let has_transparent = transparent.iter().any(|&t| t);
+  let opaque = transparent.map(|t| !t);
+  // 색 끝점만 맞추므로 알파는 0으로 둡니다.
+  let points = block.map(|p| Vec4::new(p[0], p[1], p[2], 0.0));
+
+  let encode = |e0: Vec4, e1: Vec4| {
+    let (mut c0, mut c1) = (to_565(e0), to_565(e1));
+    // 투명 픽셀이 있으면 3색 모드(c0 <= c1), 없으면 4색 모드(c0 > c1)를 사용
+    let swapped = (has_transparent && c0 > c1) || (!has_transparent && c0 < c1);
+    if swapped {
+      std::mem::swap(&mut c0, &mut c1);
+    }
+    let four_color = four_color_only || c0 > c1;
+    let palette = bc1_palette(c0, c1, four_color);
+    let ratios = if four_color {
+      [0.0, 1.0, 1.0 / 3.0, 2.0 / 3.0]
+    } else {
+      [0.0, 1.0, 0.5, 0.0]
+    };
+    let usable = if four_color { 4 } else { 3 };
+    let rgb: Vec<Vec4> = palette[..usable].iter().map(|c| c.with_w(0.0)).collect();
+
+    let mut indices = 0u32;
+    let mut error = 0.0;
+    let mut weights = [None; 16];
+    for k in 0..16 {
+      let index = if transparent[k] {
+        3
+      } else {
+        let (index, e) = nearest(points[k], &rgb);
+        error += e;
+        // 끝점을 바꿨다면 보간 비율도 원래 e0 기준으로 되돌립니다.
+        weights[k] = Some(if swapped {
+          1.0 - ratios[index]
+        } else {
+          ratios[index]
+        });
+        index
+      };
+      indices |= (index as u32) << (2 * k);
+    }
+
+    let mut bytes = [0u8; 8];
+    bytes[0..2].copy_from_slice(&c0.to_le_bytes());
+    bytes[2..4].copy_from_slice(&c1.to_le_bytes());
+    bytes[4..8].copy_from_slice(&indices.to_le_bytes());
+    Fit {
+      block: bytes,
+      error,
+      weights,
+    }
+  };
+
+  fit_endpoints(&points, &opaque, quality, encode).block
+}
+
+fn decode_bc1(bytes: &[u8], four_color_only: bool) -> [[f32; 4]; 16] {
+  let c0 = u16::from_le_bytes([bytes[0], bytes[1]]);
+  let c1 = u16::from_le_bytes([bytes[2], bytes[3]]);
+  let indices = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+  let palette = bc1_palette(c0, c1, four_color_only || c0 > c1);
+  std::array::from_fn(|k| palette[(indices >> (2 * k)) as usize & 3].to_array())
+}
+
+// BC4 단일 채널 블록
+
+// r0 > r1: 8단계 보간, 그렇지 않으면 6단계 보간 + 0과 255
+fn bc4_palette(r0: u8, r1: u8) -> [f32; 8] {
+  let (a, b) = (r0 as f32, r1 as f32);
+  let mut palette = [a, b, 0.0, 0.0, 0.0, 0.0, 0.0, 255.0];
+  if r0 > r1 {
+    for (i, v) in palette.iter_mut().enumerate().skip(2) {
+      let t = (i - 1) as f32;
+      *v = ((7.0 - t) * a + t * b) / 7.0;
+    }
+  } else {
+    for (i, v) in palette.iter_mut().enumerate().take(6).skip(2) {
+      let t = (i - 1) as f32;
+      *v = ((5.0 - t) * a + t * b) / 5.0;
+    }
+  }
+  palette.map(|v| v.round() / 255.0)
+}
+
+fn bc4_ratios(eight_values: bool) -> [Option<f32>; 8] {
+  if eight_values {
+    std::array::from_fn(|i| match i {
+      0 => Some(0.0),
+      1 => Some(1.0),
+      _ => Some((i - 1) as f32 / 7.0),
+    })
+  } else {
+    std::array::from_fn(|i| match i {
+      0 => Some(0.0),
+      1 => Some(1.0),
+      2..=5 => Some((i - 1) as f32 / 5.0),
+      _ => None,
+    })
+  }
+}
+
+fn encode_bc4(values: [f32; 16], quality: BcQuality) -> [u8; 8] {
+  let points = values.map(|v| Vec4::new(v, 0.0, 0.0, 0.0));
+  let mask = [true; 16];
+
+  let encode_mode = |e0: Vec4, e1: Vec4, eight_values: bool| {
+    let (mut r0, mut r1) = (to_u8(e0.x), to_u8(e1.x));
+    let swapped = (eight_values && r0 < r1) || (!eight_values && r0 > r1);
+    if swapped {
+      std::mem::swap(&mut r0, &mut r1);
+    }
+    let palette = bc4_palette(r0, r1).map(|v| Vec4::new(v, 0.0, 0.0, 0.0));
+    let ratios = bc4_ratios(r0 > r1);
+
+    let mut indices = 0u64;
+    let mut error = 0.0;
+    let mut weights = [None; 16];
+    for (k, p) in points.iter().enumerate() {
+      let (index, e) = nearest(*p, &palette);
+      error += e;
+      weights[k] = ratios[index].map(|t| if swapped { 1.0 - t } else { t });
+      indices |= (index as u64) << (3 * k);
+    }
+
+    let mut bytes = [0u8; 8];
+    bytes[0] = r0;
+    bytes[1] = r1;
+    bytes[2..8].copy_from_slice(&indices.to_le_bytes()[..6]);
+    Fit {
+      block: bytes,
+      error,
+      weights,
+    }
+  };
+
+  let eight = fit_endpoints(&points, &mask, quality, |e0, e1| encode_mode(e0, e1, true));
+  if quality == BcQuality::Fast {
+    return eight.block;
+  }
+
+  // 0이나 1에 가까운 값이 있으면 6단계 모드가 더 정확할 수 있습니다.
+  let six = fit_endpoints(&points, &mask, quality, |e0, e1| encode_mode(e0, e1, false));
+  if six.error < eight.error {
+    six.block
+  } else {
+    eight.block
+  }
+}
+
+fn decode_bc4(bytes: &[u8]) -> [f32; 16] {
+  let palette = bc4_palette(bytes[0], bytes[1]);
+  let mut bits = [0u8; 8];
+  bits[..6].copy_from_slice(&bytes[2..8]);
+  let indices = u64::from_le_bytes(bits);
+  std::array::from_fn(|k| palette[(indices >> (3 * k)) as usize & 7])
+}
+
+// BC7 모드 6: 하나의 부분 집합, RGBA 7비트 끝점 + 끝점별 p비트, 4비트 인덱스
+
+const BC7_WEIGHTS: [u32; 16] = [0, 4, 9, 13, 17, 21, 26, 30, 34, 38, 43, 47, 51, 55, 60, 64];
+
+struct BitWriter {
+  bits: u128,
+  position: u32,
+}
+
+impl BitWriter {
+  fn write(&mut self, value: u32, count: u32) {
+    self.bits |= ((value & ((1 << count) - 1)) as u128) << self.position;
+    self.position += count;
+  }
+}
+
+struct BitReader {
+  bits: u128,
+  position: u32,
+}
+
+impl BitReader {
+  fn read(&mut self, count: u32) -> u32 {
+    let value = (self.bits >> self.position) as u32 & ((1 << count) - 1);
+    self.position += count;
+    value
+  }
+}
+
+// 8비트 값 v에 가장 가까운 (7비트 값 << 1) | p
+fn quantize_bc7(value: f32, p: u32) -> u32 {
+  (((value * 255.0 - p as f32) / 2.0).round() as i32).clamp(0, 127) as u32
+}
+
+fn bc7_palette(q0: [u32; 4], q1: [u32; 4], p0: u32, p1: u32) -> [Vec4; 16] {
+  let e0 = q0.map(|q| (q << 1) | p0);
+  let e1 = q1.map(|q| (q << 1) | p1);
+  std::array::from_fn(|i| {
+    let w = BC7_WEIGHTS[i];
+    let c: [f32; 4] =
+      std::array::from_fn(|k| (((64 - w) * e0[k] + w * e1[k] + 32) >> 6) as f32 / 255.0);
+    Vec4::from_array(c)
+  })
+}
+
+fn encode_bc7(block: &[[f32; 4]; 16], quality: BcQuality) -> [u8; 16] {
+  let points = block.map(|p| Vec4::from_array(p).clamp(Vec4::ZERO, Vec4::ONE));
+
+  let encode_with_pbits = |e0: Vec4, e1: Vec4, p0: u32, p1: u32| {
+    let mut q0 = e0.to_array().map(|v| quantize_bc7(v, p0));
+    let mut q1 = e1.to_array().map(|v| quantize_bc7(v, p1));
+    let palette = bc7_palette(q0, q1, p0, p1);
+
+    let mut indices = [0u32; 16];
+    let mut error = 0.0;
+    for (k, p) in points.iter().enumerate() {
+      let (index, e) = nearest(*p, &palette);
+      indices[k] = index as u32;
+      error += e;
+    }
+
+    // 첫 픽셀(앵커)의 인덱스 최상위 비트는 저장하지 않으므로 0이 되도록 끝점을 바꿉니다.
+    let (mut p0, mut p1) = (p0, p1);
+    let swapped = indices[0] >= 8;
+    if swapped {
+      std::mem::swap(&mut q0, &mut q1);
+      std::mem::swap(&mut p0, &mut p1);
+      indices = indices.map(|i| 15 - i);
+    }
+
+    let mut writer = BitWriter {
+      bits: 0,
+      position: 0,
+    };
+    writer.write(1 << 6, 7);
+    for c in 0..4 {
+      writer.write(q0[c], 7);
+      writer.write(q1[c], 7);
+    }
+    writer.write(p0, 1);
+    writer.write(p1, 1);
+    for (k, index) in indices.iter().enumerate() {
+      writer.write(*index, if k == 0 { 3 } else { 4 });
+    }
+
+    let weights = std::array::from_fn(|k| {
+      let t = BC7_WEIGHTS[indices[k] as usize] as f32 / 64.0;
+      Some(if swapped { 1.0 - t } else { t })
+    });
+    Fit {
+      block: writer.bits.to_le_bytes(),
+      error,
+      weights,
+    }
+  };
+
+  let encode = |e0: Vec4, e1: Vec4| match quality {
+    // 각 끝점의 양자화 오차가 작은 p비트를 따로 고릅니다.
+    BcQuality::Fast => {
+      let pbit = |e: Vec4| {
+        let cost = |p: u32| {
+          e.to_array()
+            .iter()
+            .map(|&v| {
+              let q = ((quantize_bc7(v, p) << 1) | p) as f32 / 255.0;
+              (q - v).powi(2)
+            })
+            .sum::<f32>()
+        };
+        if cost(1) < cost(0) {
+          1
+        } else {
+          0
+        }
+      };
+      encode_with_pbits(e0, e1, pbit(e0), pbit(e1))
+    }
+    // 네 가지 p비트 조합을 모두 시도
+    BcQuality::High => [(0, 0), (0, 1), (1, 0), (1, 1)]
+      .into_iter()
+      .map(|(p0, p1)| encode_with_pbits(e0, e1, p0, p1))
+      .min_by(|a, b| a.error.total_cmp(&b.error))
+      .unwrap(),
+  };
+
+  fit_endpoints(&points, &[true; 16], quality, encode).block
+}
+
+fn decode_bc7(bytes: &[u8]) -> [[f32; 4]; 16] {
+  let mut reader = BitReader {
+    bits: u128::from_le_bytes(bytes.try_into().unwrap()),
+    position: 0,
+  };
+
+  let mode = bytes[0].trailing_zeros();
+  assert!(mode == 6, "Only BC7 mode 6 blocks can be decoded");
+  reader.read(7);
+
+  let (mut q0, mut q1) = ([0; 4], [0; 4]);
+  for c in 0..4 {
+    q0[c] = reader.read(7);
+    q1[c] = reader.read(7);
+  }
+  let (p0, p1) = (reader.read(1), reader.read(1));
+  let palette = bc7_palette(q0, q1, p0, p1);
+
+  std::array::from_fn(|k| {
+    let index = reader.read(if k == 0 { 3 } else { 4 });
+    palette[index as usize].to_array()
+  })
+}
+
+fn encode_block(format: BcFormat, block: &[[f32; 4]; 16], quality: BcQuality) -> Vec<u8> {
+  let channel = |c: usize| block.map(|p| p[c]);
+  match format {
+    BcFormat::Bc1 => encode_bc1(block, quality, false).to_vec(),
+    BcFormat::Bc3 => [
+      encode_bc4(channel(3), quality),
+      encode_bc1(block, quality, true),
+    ]
+    .concat(),
+    BcFormat::Bc4 => encode_bc4(channel(0), quality).to_vec(),
+    BcFormat::Bc5 => [
+      encode_bc4(channel(0), quality),
+      encode_bc4(channel(1), quality),
+    ]
+    .concat(),
+    BcFormat::Bc7 => encode_bc7(block, quality).to_vec(),
+  }
+}
+
+fn decode_block(format: BcFormat, bytes: &[u8]) -> [[f32; 4]; 16] {
+  match format {
+    BcFormat::Bc1 => decode_bc1(bytes, false),
+    BcFormat::Bc3 => {
+      let alpha = decode_bc4(&bytes[..8]);
+      let mut color = decode_bc1(&bytes[8..], true);
+      for (pixel, a) in color.iter_mut().zip(alpha) {
+        pixel[3] = a;
+      }
+      color
+    }
+    BcFormat::Bc4 => decode_bc4(bytes).map(|r| [r, 0.0, 0.0, 1.0]),
+    BcFormat::Bc5 => {
+      let (r, g) = (decode_bc4(&bytes[..8]), decode_bc4(&bytes[8..]));
+      std::array::from_fn(|k| [r[k], g[k], 0.0, 1.0])
+    }
+    BcFormat::Bc7 => decode_bc7(bytes),
+  }
+}
+
+impl CompressedImage {
+  pub fn blocks_x(&self) -> u32 {
+    self.width.div_ceil(4)
+  }
+
+  pub fn blocks_y(&self) -> u32 {
+    self.height.div_ceil(4)
+  }
+
+  // wgpu::TexelCopyBufferLayout::bytes_per_row에 넣을 값
+  pub fn bytes_per_row(&self) -> u32 {
+    self.blocks_x() * self.format.block_size() as u32
+  }
+
+  pub fn decode(&self) -> Image {
+    let mut image = Image::new(self.width, self.height);
+    let block_size = self.format.block_size();
+
+    for (index, bytes) in self.data.chunks_exact(block_size).enumerate() {
+      let (bx, by) = (
+        index as u32 % self.blocks_x(),
+        index as u32 / self.blocks_x(),
+      );
+      for (k, pixel) in decode_block(self.format, bytes).iter().enumerate() {
+        let (x, y) = (bx * 4 + k as u32 % 4, by * 4 + k as u32 / 4);
+        if x < self.width && y < self.height {
+          image.pixels[(x + y * self.width) as usize] = *pixel;
+        }
+      }
+    }
+    image
+  }
+
+  // 압축 형식이 저장하는 채널만 비교한 PSNR (dB)
+  pub fn psnr(&self, original: &Image) -> f32 {
+    self.decode().psnr(original, self.format.channels())
+  }
+}
+
+impl Image {
+  // 가장자리의 불완전한 블록은 마지막 행과 열을 반복해서 채웁니다.
+  pub fn encode_bc(&self, format: BcFormat, quality: BcQuality) -> CompressedImage {
+    let (blocks_x, blocks_y) = (self.width.div_ceil(4), self.height.div_ceil(4));
+    let mut data = Vec::with_capacity((blocks_x * blocks_y) as usize * format.block_size());
+
+    for by in 0..blocks_y as i32 {
+      for bx in 0..blocks_x as i32 {
+        let block = std::array::from_fn(|k| {
+          let (x, y) = (bx * 4 + k as i32 % 4, by * 4 + k as i32 / 4);
+          *self.get_pixel(x, y)
+        });
+        data.extend(encode_block(format, &block, quality));
+      }
+    }
+
+    CompressedImage {
+      format,
+      width: self.width,
+      height: self.height,
+      data,
+    }
+  }
+
+  // 앞쪽 channels개 채널의 최대값 1.0 기준 PSNR (같은 이미지이면 무한대)
+  pub fn psnr(&self, other: &Image, channels: usize) -> f32 {
+    assert!(
+      self.width == other.width && self.height == other.height,
+      "Image size mismatch"
+    );
+
+    let mut sum = 0.0;
+    for (a, b) in self.pixels.iter().zip(&other.pixels) {
+      for c in 0..channels {
+        sum += (a[c].clamp(0.0, 1.0) - b[c].clamp(0.0, 1.0)).powi(2) as f64;
+      }
+    }
+    let mse = sum / (self.pixels.len() * channels) as f64;
+    if mse == 0.0 {
+      f32::INFINITY
+    } else {
+      (10.0 * (1.0 / mse).log10()) as f32
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::image::noise::Noise;
+
+  const FORMATS: [BcFormat; 5] = [
+    BcFormat::Bc1,
+    BcFormat::Bc3,
+    BcFormat::Bc4,
+    BcFormat::Bc5,
+    BcFormat::Bc7,
+  ];
+
+  #[test]
+  fn test_payload_size() {
+    let image = Image::new(10, 6);
+    for format in FORMATS {
+      let compressed = image.encode_bc(format, BcQuality::Fast);
+      assert_eq!(compressed.data.len(), 3 * 2 * format.block_size());
+      assert_eq!(compressed.bytes_per_row() as usize, 3 * format.block_size());
+      assert_eq!((compressed.blocks_x(), compressed.blocks_y()), (3, 2));
+
+      // wgpu 형식의 블록 크기와 일치해야 그대로 업로드할 수 있습니다.
+      let texture_format = format.texture_format(false);
+      assert_eq!(
+        texture_format.block_copy_size(None),
+        Some(format.block_size() as u32)
+      );
+      assert!(!texture_format.is_srgb());
+    }
+    assert!(BcFormat::Bc7.texture_format(true).is_srgb());
+  }
+
+  #[test]
+  fn test_solid_color_round_trip() {
+    let mut image = Image::new(8, 8);
+    image.as_view_mut().fill([0.2, 0.6, 0.8, 1.0]);
+
+    for format in FORMATS {
+      for quality in [BcQuality::Fast, BcQuality::High] {
+        let compressed = image.encode_bc(format, quality);
+        assert!(
+          compressed.psnr(&image) > 38.0,
+          "{:?} {:?}: {}",
+          format,
+          quality,
+          compressed.psnr(&image)
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn test_quality_on_gradients_and_noise() {
+    let gradient = Image::linear_gradient(16, 16, [0.0, 0.2, 1.0, 1.0], [1.0, 0.8, 0.0, 1.0]);
+    for format in FORMATS {
+      let psnr = gradient.encode_bc(format, BcQuality::High).psnr(&gradient);
+      assert!(psnr > 35.0, "{:?}: {}", format, psnr);
+    }
+
+    // 부드러운 알파는 BC3과 BC7만 저장할 수 있습니다.
+    let fade = Image::linear_gradient(16, 16, [1.0, 0.5, 0.0, 1.0], [1.0, 0.5, 0.0, 0.0]);
+    for format in [BcFormat::Bc3, BcFormat::Bc7] {
+      let psnr = fade.encode_bc(format, BcQuality::High).psnr(&fade);
+      assert!(psnr > 35.0, "{:?}: {}", format, psnr);
+    }
+
+    let noise = Image::fbm_noise(32, 32, Noise::Perlin, 3, 4, Default::default());
+    for format in FORMATS {
+      let fast = noise.encode_bc(format, BcQuality::Fast).psnr(&noise);
+      let high = noise.encode_bc(format, BcQuality::High).psnr(&noise);
+      assert!(high >= fast - 0.1, "{:?}: {} < {}", format, high, fast);
+    }
+
+    // BC7은 같은 크기의 BC3보다 정확해야 합니다.
+    let bc3 = noise.encode_bc(BcFormat::Bc3, BcQuality::High).psnr(&noise);
+    let bc7 = noise.encode_bc(BcFormat::Bc7, BcQuality::High).psnr(&noise);
+    assert!(bc7 > bc3, "{} <= {}", bc7, bc3);
+  }
+
+  #[test]
+  fn test_bc1_punch_through_alpha() {
+    let mut image = Image::new(4, 4);
+    image.as_view_mut().fill([1.0, 0.5, 0.0, 1.0]);
+    *image.get_pixel_mut(1, 2) = [0.0; 4];
+
+    let decoded = image.encode_bc(BcFormat::Bc1, BcQuality::High).decode();
+    assert_eq!(decoded.get_pixel(1, 2)[3], 0.0);
+    assert_eq!(decoded.get_pixel(0, 0)[3], 1.0);
+    assert!((decoded.get_pixel(0, 0)[1] - 0.5).abs() < 0.02);
+  }
+}
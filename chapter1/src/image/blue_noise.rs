@@ -0,0 +1,280 @@
+use super::noise::Rng;
+use super::Image;
+use std::f32::consts::TAU;
+
+// 보이드 앤 클러스터에서 점 사이의 밀집도를 재는 가우시안의 시그마 (Ulichney 권장값)
+const VOID_AND_CLUSTER_SIGMA: f32 = 1.5;
+// 처음 무작위로 찍는 점의 비율
+const INITIAL_DENSITY: f32 = 0.1;
+// Bridson 알고리즘에서 활성 점 하나당 시도할 후보 수
+const POISSON_ATTEMPTS: u32 = 30;
+
+// 토러스(가장자리가 이어진) 위에서 각 점이 주변에 주는 밀집도
+struct EnergyField {
+  width: usize,
+  height: usize,
+  // (dx, dy) 거리의 가우시안 값
+  kernel: Vec<f32>,
+  energy: Vec<f32>,
+  ones: Vec<bool>,
+}
+
+impl EnergyField {
+  fn new(width: usize, height: usize) -> Self {
+    let wrap = |d: usize, n: usize| d.min(n - d) as f32;
+    let kernel = (0..width * height)
+      .map(|k| {
+        let (dx, dy) = (wrap(k % width, width), wrap(k / width, height));
+        (-(dx * dx + dy * dy) / (2.0 * VOID_AND_CLUSTER_SIGMA * VOID_AND_CLUSTER_SIGMA)).exp()
+      })
+      .collect();
+
+    Self {
+      width,
+      height,
+      kernel,
+      energy: vec![0.0; width * height],
+      ones: vec![false; width * height],
+    }
+  }
+
+  fn set(&mut self, k: usize, one: bool) {
+    if self.ones[k] == one {
+      return;
+    }
+    self.ones[k] = one;
+
+    let sign = if one { 1.0 } else { -1.0 };
+    let (x, y) = (k % self.width, k / self.width);
+    for (q, e) in self.energy.iter_mut().enumerate() {
+      let dx = (q % self.width + self.width - x) % self.width;
+      let dy = (q / self.width + self.height - y) % self.height;
+      *e += sign * self.kernel[dx + dy * self.width];
+    }
+  }
+
+  // 점이 가장 빽빽하게 모인 곳
+  fn tightest_cluster(&self) -> usize {
+    (0..self.ones.len())
+      .filter(|&k| self.ones[k])
+      .max_by(|&a, &b| self.energy[a].total_cmp(&self.energy[b]).then(b.cmp(&a)))
+      .unwrap()
+  }
+
+  // 점에서 가장 멀리 떨어진 빈 곳
+  fn largest_void(&self) -> usize {
+    (0..self.ones.len())
+      .filter(|&k| !self.ones[k])
+      .min_by(|&a, &b| self.energy[a].total_cmp(&self.energy[b]).then(a.cmp(&b)))
+      .unwrap()
+  }
+}
+
+// 토러스 [0, width) x [0, height) 위에서 서로 radius 이상 떨어진 점들 (Bridson, 2007)
+// 영역이 이어져 있으므로 결과를 타일처럼 반복해도 간격이 유지됩니다.
+pub fn poisson_disk(width: f32, height: f32, radius: f32, seed: u64) -> Vec<(f32, f32)> {
+  assert!(
+    radius > 0.0 && width >= radius * 2.0 && height >= radius * 2.0,
+    "Domain must be at least twice the radius"
+  );
+
+  // 칸의 대각선이 radius 이하이므로 한 칸에는 점이 하나만 들어갑니다.
+  let cell = radius / 2.0f32.sqrt();
+  let (columns, rows) = ((width / cell).ceil() as i32, (height / cell).ceil() as i32);
+  let mut grid: Vec<Option<usize>> = vec![None; (columns * rows) as usize];
+  let cell_index = |(x, y): (f32, f32)| {
+    let (i, j) = ((x / cell) as i32 % columns, (y / cell) as i32 % rows);
+    (i + j * columns) as usize
+  };
+
+  let distance_squared = |a: (f32, f32), b: (f32, f32)| {
+    let dx = (a.0 - b.0).abs();
+    let dy = (a.1 - b.1).abs();
+    let (dx, dy) = (dx.min(width - dx), dy.min(height - dy));
+    dx * dx + dy * dy
+  };
+
+  let mut rng = Rng::new(seed);
+  let first = (rng.next_f32() * width, rng.next_f32() * height);
+  let mut points = vec![first];
+  let mut active = vec![0];
+  grid[cell_index(first)] = Some(0);
+
+  while !active.is_empty() {
+    let slot = rng.below(active.len() as u32) as usize;
+    let center = points[active[slot]];
+
+    let mut found = false;
+    for _ in 0..POISSON_ATTEMPTS {
+      // [radius, 2 * radius) 고리 안에서 면적에 고르게 후보를 뽑습니다.
+      let r = radius * (1.0 + 3.0 * rng.next_f32()).sqrt();
+      let angle = rng.next_f32() * TAU;
+      let candidate = (
+        (center.0 + r * angle.cos()).rem_euclid(width),
+        (center.1 + r * angle.sin()).rem_euclid(height),
+      );
+
+      // 가장자리의 칸은 잘려서 작을 수 있으므로 한 칸 더 넓게 살펴봅니다.
+      let index = cell_index(candidate) as i32;
+      let (ci, cj) = (index % columns, index / columns);
+      let far_enough = (-3..=3).all(|dj| {
+        (-3..=3).all(|di| {
+          let k = (ci + di).rem_euclid(columns) + (cj + dj).rem_euclid(rows) * columns;
+          grid[k as usize].is_none_or(|p| distance_squared(points[p], candidate) >= radius * radius)
+        })
+      });
+
+      if far_enough {
+        grid[index as usize] = Some(points.len());
+        active.push(points.len());
+        points.push(candidate);
+        found = true;
+        break;
+      }
+    }
+
+    if !found {
+      active.swap_remove(slot);
+    }
+  }
+  points
+}
+
+impl Image {
+  // 보이드 앤 클러스터(Ulichney, 1993)로 만든 타일링 가능한 블루 노이즈 문턱값 맵
+  // 각 픽셀은 순위를 [0, 1)로 고르게 나눈 값이라서 어떤 문턱값에서도 점들이 고르게 퍼집니다.
+  pub fn blue_noise(width: u32, height: u32, seed: u64) -> Image {
+    let (w, h) = (width as usize, height as usize);
+    let n = w * h;
+    assert!(n >= 2, "Blue noise needs at least two pixels");
+
+    // 초기 무작위 패턴에서 가장 빽빽한 점을 가장 빈 곳으로 옮기는 것을 반복합니다.
+    let mut field = EnergyField::new(w, h);
+    let mut rng = Rng::new(seed);
+    let initial = ((n as f32 * INITIAL_DENSITY) as usize).clamp(1, n / 2);
+    while field.ones.iter().filter(|&&o| o).count() < initial {
+      field.set(rng.below(n as u32) as usize, true);
+    }
+    loop {
+      let cluster = field.tightest_cluster();
+      field.set(cluster, false);
+      let void = field.largest_void();
+      field.set(void, true);
+      if void == cluster {
+        break;
+      }
+    }
+
+    let mut rank = vec![0; n];
+    let prototype = field.ones.clone();
+
+    // 초기 패턴의 점들은 빽빽한 것부터 빼면서 높은 순위를 줍니다.
+    for r in (0..initial).rev() {
+      let cluster = field.tightest_cluster();
+      field.set(cluster, false);
+      rank[cluster] = r;
+    }
+
+    // 나머지는 가장 빈 곳부터 채웁니다.
+    // 절반을 넘으면 원래는 0의 가장 빽빽한 곳을 찾지만, 0의 밀집도는 1의 밀집도를 뒤집은 것이라 같은 위치입니다.
+    for (k, &one) in prototype.iter().enumerate() {
+      field.set(k, one);
+    }
+    for r in initial..n {
+      let void = field.largest_void();
+      field.set(void, true);
+      rank[void] = r;
+    }
+
+    let pixels = rank
+      .iter()
+      .map(|&r| {
+        let v = (r as f32 + 0.5) / n as f32;
+        [v, v, v, 1.0]
+      })
+      .collect();
+    Image::from_pixels(width, height, pixels)
+  }
+
+  // 문턱값 맵(블루 노이즈나 베이어 행렬)을 타일처럼 반복해서 levels 단계로 순서 디더링합니다.
+  pub fn dither(&mut self, threshold: &Image, levels: u32) {
+    let steps = (levels.max(2) - 1) as f32;
+    for j in 0..self.height {
+      for i in 0..self.width {
+        let t = threshold.pixels
+          [((i % threshold.width) + (j % threshold.height) * threshold.width) as usize][0];
+        let pixel = &mut self.pixels[(i + j * self.width) as usize];
+        for v in pixel.iter_mut().take(3) {
+          *v = ((*v * steps + t).floor() / steps).clamp(0.0, 1.0);
+        }
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::image::BorderMode;
+
+  #[test]
+  fn test_blue_noise_is_a_permutation() {
+    let noise = Image::blue_noise(16, 16, 1);
+    let mut ranks: Vec<u32> = noise.pixels.iter().map(|p| (p[0] * 256.0) as u32).collect();
+    ranks.sort();
+    assert_eq!(ranks, (0..256).collect::<Vec<_>>());
+
+    assert_eq!(noise.pixels, Image::blue_noise(16, 16, 1).pixels);
+    assert_ne!(noise.pixels, Image::blue_noise(16, 16, 2).pixels);
+  }
+
+  #[test]
+  fn test_blue_noise_spreads_points() {
+    // 가장 낮은 1/8의 점들은 이웃한 픽셀에 붙어 있지 않습니다 (타일 경계를 넘어서도).
+    let (w, h) = (32, 32);
+    let noise = Image::blue_noise(w, h, 5);
+    let on = |i: i32, j: i32| noise.get_pixel_border(i, j, BorderMode::Wrap)[0] < 0.125;
+
+    let mut adjacent = 0;
+    for j in 0..h as i32 {
+      for i in 0..w as i32 {
+        if on(i, j) && (on(i + 1, j) || on(i, j + 1)) {
+          adjacent += 1;
+        }
+      }
+    }
+    assert_eq!(adjacent, 0);
+  }
+
+  #[test]
+  fn test_poisson_disk_spacing() {
+    let (width, height, radius) = (40.0, 30.0, 3.0);
+    let points = poisson_disk(width, height, radius, 9);
+    assert_eq!(points, poisson_disk(width, height, radius, 9));
+
+    // 타일 경계를 넘어서도 최소 거리를 지킵니다.
+    for (k, a) in points.iter().enumerate() {
+      assert!(a.0 >= 0.0 && a.0 < width && a.1 >= 0.0 && a.1 < height);
+      for b in points.iter().skip(k + 1) {
+        let dx = (a.0 - b.0).abs().min(width - (a.0 - b.0).abs());
+        let dy = (a.1 - b.1).abs().min(height - (a.1 - b.1).abs());
+        assert!(dx * dx + dy * dy >= radius * radius - 1e-3);
+      }
+    }
+
+    // 최대한 채워졌으므로 점이 충분히 많습니다. (육각 배치의 절반 이상)
+    let hexagonal = width * height / (radius * radius * 3.0f32.sqrt() / 2.0);
+    assert!(points.len() as f32 > hexagonal * 0.5, "{}", points.len());
+  }
+
+  #[test]
+  fn test_dither_preserves_mean() {
+    let threshold = Image::blue_noise(16, 16, 3);
+    let mut image = Image::new(32, 32);
+    image.as_view_mut().fill([0.3, 0.3, 0.3, 1.0]);
+    image.dither(&threshold, 2);
+
+    assert!(image.pixels.iter().all(|p| p[0] == 0.0 || p[0] == 1.0));
+    assert!((image.as_view().mean()[0] - 0.3).abs() < 0.01);
+  }
+}
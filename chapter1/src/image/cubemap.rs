@@ -0,0 +1,308 @@
+use super::{BorderMode, Image};
+use glam::Vec3;
+use std::f32::consts::{PI, TAU};
+
+// 면 순서는 wgpu/D3D 큐브맵 레이어 순서인 +X, -X, +Y, -Y, +Z, -Z 입니다.
+pub struct Cubemap {
+  pub faces: [Image; 6],
+}
+
+// 면 위의 좌표 (u, v) ∈ [-1, 1] (v는 아래 방향)을 방향 벡터로 변환
+pub fn face_direction(face: usize, u: f32, v: f32) -> Vec3 {
+  let dir = match face {
+    0 => Vec3::new(1.0, -v, -u),
+    1 => Vec3::new(-1.0, -v, u),
+    2 => Vec3::new(u, 1.0, v),
+    3 => Vec3::new(u, -1.0, -v),
+    4 => Vec3::new(u, -v, 1.0),
+    5 => Vec3::new(-u, -v, -1.0),
+    _ => panic!("Invalid cubemap face: {}", face),
+  };
+  dir.normalize()
+}
+
+// 방향 벡터가 가리키는 면과 그 면 위의 좌표 (u, v) ∈ [-1, 1]
+pub fn direction_to_face(dir: Vec3) -> (usize, f32, f32) {
+  let abs = dir.abs();
+  if abs.x >= abs.y && abs.x >= abs.z {
+    if dir.x > 0.0 {
+      (0, -dir.z / abs.x, -dir.y / abs.x)
+    } else {
+      (1, dir.z / abs.x, -dir.y / abs.x)
+    }
+  } else if abs.y >= abs.z {
+    if dir.y > 0.0 {
+      (2, dir.x / abs.y, dir.z / abs.y)
+    } else {
+      (3, dir.x / abs.y, -dir.z / abs.y)
+    }
+  } else if dir.z > 0.0 {
+    (4, dir.x / abs.z, -dir.y / abs.z)
+  } else {
+    (5, -dir.x / abs.z, -dir.y / abs.z)
+  }
+}
+
+// 등장방형(equirectangular) 텍스처 좌표 (u, v) ∈ [0, 1]을 방향으로 변환
+// u = 0.5가 -Z 방향, v = 0이 +Y(위) 방향입니다.
+pub fn equirect_direction(u: f32, v: f32) -> Vec3 {
+  let phi = (u - 0.5) * TAU;
+  let theta = v * PI;
+  Vec3::new(
+    theta.sin() * phi.sin(),
+    theta.cos(),
+    -theta.sin() * phi.cos(),
+  )
+}
+
+pub fn direction_to_equirect(dir: Vec3) -> (f32, f32) {
+  let dir = dir.normalize();
+  let u = 0.5 + dir.x.atan2(-dir.z) / TAU;
+  let v = dir.y.clamp(-1.0, 1.0).acos() / PI;
+  (u, v)
+}
+
+// 가로는 감싸고 세로는 극에서 고정하는 등장방형 이미지 샘플링
+fn sample_equirect(image: &Image, dir: Vec3) -> [f32; 4] {
+  let (u, v) = direction_to_equirect(dir);
+  let x = u * image.width as f32 - 0.5;
+  let y = (v * image.height as f32 - 0.5).clamp(0.0, image.height as f32 - 1.0);
+  image.sample_bilinear(x, y, BorderMode::Wrap)
+}
+
+// 텍셀 중심의 면 좌표
+fn texel_uv(index: u32, size: u32) -> f32 {
+  2.0 * (index as f32 + 0.5) / size as f32 - 1.0
+}
+
+// 면 좌표 (0, 0)부터 (x, y)까지의 영역이 단위 구에서 차지하는 입체각
+fn area_element(x: f32, y: f32) -> f32 {
+  (x * y).atan2((x * x + y * y + 1.0).sqrt())
+}
+
+fn texel_solid_angle(i: u32, j: u32, size: u32) -> f32 {
+  let half = 1.0 / size as f32;
+  let (u, v) = (texel_uv(i, size), texel_uv(j, size));
+  let (x0, x1, y0, y1) = (u - half, u + half, v - half, v + half);
+  area_element(x0, y0) - area_element(x0, y1) - area_element(x1, y0) + area_element(x1, y1)
+}
+
+fn radical_inverse(mut bits: u32) -> f32 {
+  bits = bits.reverse_bits();
+  bits as f32 * 2.328_306_4e-10
+}
+
+// GGX 분포에 따라 중요도 샘플링한 하프 벡터
+fn importance_sample_ggx(xi: (f32, f32), normal: Vec3, roughness: f32) -> Vec3 {
+  let a = roughness * roughness;
+  let phi = TAU * xi.0;
+  let cos_theta = ((1.0 - xi.1) / (1.0 + (a * a - 1.0) * xi.1)).sqrt();
+  let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+  let h = Vec3::new(phi.cos() * sin_theta, phi.sin() * sin_theta, cos_theta);
+
+  // 탄젠트 공간에서 월드 공간으로 변환
+  let up = if normal.z.abs() < 0.999 {
+    Vec3::Z
+  } else {
+    Vec3::X
+  };
+  let tangent = up.cross(normal).normalize();
+  let bitangent = normal.cross(tangent);
+  (tangent * h.x + bitangent * h.y + normal * h.z).normalize()
+}
+
+impl Cubemap {
+  pub fn new(face_size: u32) -> Self {
+    Self {
+      faces: std::array::from_fn(|_| Image::new(face_size, face_size)),
+    }
+  }
+
+  pub fn face_size(&self) -> u32 {
+    self.faces[0].width
+  }
+
+  // 각 텍셀 중심의 방향으로 f를 호출해서 큐브맵을 채웁니다.
+  pub fn from_fn(face_size: u32, f: impl Fn(Vec3) -> [f32; 4]) -> Self {
+    let mut cubemap = Self::new(face_size);
+    for (face, image) in cubemap.faces.iter_mut().enumerate() {
+      for j in 0..face_size {
+        for i in 0..face_size {
+          let dir = face_direction(face, texel_uv(i, face_size), texel_uv(j, face_size));
+          image.pixels[(i + j * face_size) as usize] = f(dir);
+        }
+      }
+    }
+    cubemap
+  }
+
+  pub fn from_equirect(image: &Image, face_size: u32) -> Self {
+    Self::from_fn(face_size, |dir| sample_equirect(image, dir))
+  }
+
+  pub fn to_equirect(&self, width: u32, height: u32) -> Image {
+    let mut image = Image::new(width, height);
+    for j in 0..height {
+      for i in 0..width {
+        let u = (i as f32 + 0.5) / width as f32;
+        let v = (j as f32 + 0.5) / height as f32;
+        image.pixels[(i + j * width) as usize] = self.sample(equirect_direction(u, v));
+      }
+    }
+    image
+  }
+
+  // 방향 dir에 해당하는 면에서 쌍선형 보간 (면의 경계에서는 고정)
+  pub fn sample(&self, dir: Vec3) -> [f32; 4] {
+    let (face, u, v) = direction_to_face(dir);
+    let size = self.face_size() as f32;
+    let x = (u + 1.0) * 0.5 * size - 0.5;
+    let y = (v + 1.0) * 0.5 * size - 0.5;
+    self.faces[face].sample_bilinear(x, y, BorderMode::Clamp)
+  }
+
+  // 확산(diffuse) 조도 맵: E(n) = 1/π ∫ L(ω) max(0, n·ω) dω
+  // 계산량이 면 크기의 네제곱에 비례하므로 입력은 source_size로 줄여서 적분합니다.
+  pub fn irradiance(&self, face_size: u32, source_size: u32) -> Self {
+    let source_size = source_size.min(self.face_size()).max(1);
+    let mut samples = Vec::with_capacity((6 * source_size * source_size) as usize);
+    for face in 0..6 {
+      for j in 0..source_size {
+        for i in 0..source_size {
+          let dir = face_direction(face, texel_uv(i, source_size), texel_uv(j, source_size));
+          let radiance = self.sample(dir);
+          let weight = texel_solid_angle(i, j, source_size) / PI;
+          samples.push((dir, radiance, weight));
+        }
+      }
+    }
+
+    Self::from_fn(face_size, |normal| {
+      let mut sum = [0.0, 0.0, 0.0];
+      for (dir, radiance, weight) in samples.iter() {
+        let cos = normal.dot(*dir);
+        if cos > 0.0 {
+          for c in 0..3 {
+            sum[c] += radiance[c] * cos * weight;
+          }
+        }
+      }
+      [sum[0], sum[1], sum[2], 1.0]
+    })
+  }
+
+  // GGX 스페큘러 사전 필터링 밉 체인 (N = V = R 근사)
+  // 밉 레벨 k의 거칠기는 k / (mip_levels - 1)이고 면 크기는 face_size >> k 입니다.
+  pub fn prefilter_ggx(&self, face_size: u32, mip_levels: u32, sample_count: u32) -> Vec<Self> {
+    (0..mip_levels)
+      .map(|level| {
+        let size = (face_size >> level).max(1);
+        let roughness = if mip_levels > 1 {
+          level as f32 / (mip_levels - 1) as f32
+        } else {
+          0.0
+        };
+
+        if roughness == 0.0 {
+          return Self::from_fn(size, |dir| self.sample(dir));
+        }
+
+        Self::from_fn(size, |normal| {
+          let mut sum = [0.0, 0.0, 0.0];
+          let mut total_weight = 0.0;
+          for k in 0..sample_count {
+            let xi = (k as f32 / sample_count as f32, radical_inverse(k));
+            let h = importance_sample_ggx(xi, normal, roughness);
+            let l = 2.0 * normal.dot(h) * h - normal;
+
+            let n_dot_l = normal.dot(l);
+            if n_dot_l > 0.0 {
+              let radiance = self.sample(l);
+              for c in 0..3 {
+                sum[c] += radiance[c] * n_dot_l;
+              }
+              total_weight += n_dot_l;
+            }
+          }
+
+          let inv = if total_weight > 0.0 {
+            1.0 / total_weight
+          } else {
+            0.0
+          };
+          [sum[0] * inv, sum[1] * inv, sum[2] * inv, 1.0]
+        })
+      })
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sky(dir: Vec3) -> [f32; 4] {
+    let t = dir.y * 0.5 + 0.5;
+    [t, 0.5, 1.0 - t, 1.0]
+  }
+
+  #[test]
+  fn test_face_direction_round_trip() {
+    for face in 0..6 {
+      for (u, v) in [(0.0, 0.0), (0.5, -0.25), (-0.75, 0.9)] {
+        let (f, u2, v2) = direction_to_face(face_direction(face, u, v));
+        assert_eq!(f, face);
+        assert!((u - u2).abs() < 1e-5 && (v - v2).abs() < 1e-5);
+      }
+    }
+
+    let dir = Vec3::new(0.3, -0.4, 0.8).normalize();
+    let (u, v) = direction_to_equirect(dir);
+    assert!(equirect_direction(u, v).distance(dir) < 1e-5);
+  }
+
+  #[test]
+  fn test_equirect_cubemap_round_trip() {
+    let cubemap = Cubemap::from_fn(32, sky);
+    let equirect = cubemap.to_equirect(64, 32);
+    let back = Cubemap::from_equirect(&equirect, 16);
+
+    for face in 0..6 {
+      for (i, pixel) in back.faces[face].pixels.iter().enumerate() {
+        let (x, y) = (i as u32 % 16, i as u32 / 16);
+        let expected = sky(face_direction(face, texel_uv(x, 16), texel_uv(y, 16)));
+        for c in 0..3 {
+          assert!((pixel[c] - expected[c]).abs() < 0.05);
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn test_solid_angles_cover_sphere() {
+    let total: f32 = (0..8)
+      .flat_map(|j| (0..8).map(move |i| texel_solid_angle(i, j, 8)))
+      .sum();
+    assert!((total * 6.0 - 4.0 * PI).abs() < 1e-3);
+  }
+
+  #[test]
+  fn test_constant_environment_is_preserved() {
+    let cubemap = Cubemap::from_fn(8, |_| [0.25, 0.5, 1.0, 1.0]);
+
+    let irradiance = cubemap.irradiance(4, 8);
+    for pixel in irradiance.faces.iter().flat_map(|f| f.pixels.iter()) {
+      assert!((pixel[0] - 0.25).abs() < 0.01);
+      assert!((pixel[2] - 1.0).abs() < 0.02);
+    }
+
+    let mips = cubemap.prefilter_ggx(8, 3, 16);
+    assert_eq!(
+      mips.iter().map(|m| m.face_size()).collect::<Vec<_>>(),
+      vec![8, 4, 2]
+    );
+    for pixel in mips[2].faces.iter().flat_map(|f| f.pixels.iter()) {
+      assert!((pixel[1] - 0.5).abs() < 1e-4);
+    }
+  }
+}
@@ -0,0 +1,416 @@
+use super::Image;
+use std::collections::HashMap;
+
+// 다각형 내부를 판정하는 규칙
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FillRule {
+  EvenOdd,
+  NonZero,
+}
+
+// 샤오린 우(Xiaolin Wu)의 직선이 덮는 픽셀과 그 비율을 plot으로 넘깁니다.
+fn wu_line(x0: f32, y0: f32, x1: f32, y1: f32, mut plot: impl FnMut(i32, i32, f32)) {
+  let steep = (y1 - y0).abs() > (x1 - x0).abs();
+
+  // 기울기가 1보다 크면 x와 y를 바꿔서 항상 x 방향으로 진행
+  let (mut x0, mut y0, mut x1, mut y1) = if steep {
+    (y0, x0, y1, x1)
+  } else {
+    (x0, y0, x1, y1)
+  };
+  if x0 > x1 {
+    std::mem::swap(&mut x0, &mut x1);
+    std::mem::swap(&mut y0, &mut y1);
+  }
+
+  let dx = x1 - x0;
+  let gradient = if dx == 0.0 { 1.0 } else { (y1 - y0) / dx };
+
+  let mut plot = |x: i32, y: i32, coverage: f32| {
+    if steep {
+      plot(y, x, coverage);
+    } else {
+      plot(x, y, coverage);
+    }
+  };
+
+  // 시작점
+  let x_end = x0.round();
+  let y_end = y0 + gradient * (x_end - x0);
+  let x_gap = 1.0 - (x0 + 0.5).fract();
+  let x_start = x_end as i32;
+  let y_floor = y_end.floor();
+  plot(x_start, y_floor as i32, (1.0 - (y_end - y_floor)) * x_gap);
+  plot(x_start, y_floor as i32 + 1, (y_end - y_floor) * x_gap);
+  let mut y_inter = y_end + gradient;
+
+  // 끝점
+  let x_end = x1.round();
+  let y_end = y1 + gradient * (x_end - x1);
+  let x_gap = (x1 + 0.5).fract();
+  let x_stop = x_end as i32;
+  let y_floor = y_end.floor();
+  if x_stop != x_start {
+    plot(x_stop, y_floor as i32, (1.0 - (y_end - y_floor)) * x_gap);
+    plot(x_stop, y_floor as i32 + 1, (y_end - y_floor) * x_gap);
+  }
+
+  // 중간 구간
+  for x in x_start + 1..x_stop {
+    let y_floor = y_inter.floor();
+    let frac = y_inter - y_floor;
+    plot(x, y_floor as i32, 1.0 - frac);
+    plot(x, y_floor as i32 + 1, frac);
+    y_inter += gradient;
+  }
+}
+
+// 좌표계: 정수 좌표 (i, j)가 픽셀 (i, j)의 중심입니다.
+impl Image {
+  fn contains(&self, i: i32, j: i32) -> bool {
+    i >= 0 && j >= 0 && i < self.width as i32 && j < self.height as i32
+  }
+
+  // color의 알파에 coverage를 곱해서 source-over 블렌딩
+  // get_pixel_mut는 범위 밖 좌표를 가장자리로 고정하므로 먼저 범위를 확인합니다.
+  pub fn blend_pixel(&mut self, i: i32, j: i32, color: [f32; 4], coverage: f32) {
+    if !self.contains(i, j) {
+      return;
+    }
+
+    let alpha = color[3] * coverage.clamp(0.0, 1.0);
+    let dst = self.get_pixel_mut(i, j);
+    for c in 0..3 {
+      dst[c] = color[c] * alpha + dst[c] * (1.0 - alpha);
+    }
+    dst[3] = alpha + dst[3] * (1.0 - alpha);
+  }
+
+  // 중복된 점을 제거한 뒤 블렌딩 (반투명 색이 두 번 섞이지 않도록)
+  fn blend_points(&mut self, mut points: Vec<(i32, i32)>, color: [f32; 4]) {
+    points.sort_unstable();
+    points.dedup();
+    for (i, j) in points {
+      self.blend_pixel(i, j, color, 1.0);
+    }
+  }
+
+  fn blend_span(&mut self, i0: i32, i1: i32, j: i32, color: [f32; 4]) {
+    for i in i0.max(0)..=i1.min(self.width as i32 - 1) {
+      self.blend_pixel(i, j, color, 1.0);
+    }
+  }
+
+  // 브레젠험(Bresenham) 직선
+  pub fn draw_line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, color: [f32; 4]) {
+    let dx = (x1 - x0).abs();
+    let dy = -(y1 - y0).abs();
+    let sx = if x0 < x1 { 1 } else { -1 };
+    let sy = if y0 < y1 { 1 } else { -1 };
+
+    let (mut x, mut y) = (x0, y0);
+    let mut error = dx + dy;
+    loop {
+      self.blend_pixel(x, y, color, 1.0);
+      if x == x1 && y == y1 {
+        break;
+      }
+
+      let e2 = 2 * error;
+      if e2 >= dy {
+        error += dy;
+        x += sx;
+      }
+      if e2 <= dx {
+        error += dx;
+        y += sy;
+      }
+    }
+  }
+
+  // 샤오린 우(Xiaolin Wu)의 안티앨리어싱 직선
+  pub fn draw_line_aa(&mut self, x0: f32, y0: f32, x1: f32, y1: f32, color: [f32; 4]) {
+    wu_line(x0, y0, x1, y1, |i, j, coverage| {
+      self.blend_pixel(i, j, color, coverage)
+    });
+  }
+
+  // 중점(midpoint) 원 알고리즘
+  pub fn draw_circle(&mut self, cx: i32, cy: i32, radius: i32, color: [f32; 4]) {
+    let mut points = Vec::new();
+    let (mut x, mut y) = (radius, 0);
+    let mut error = 1 - radius;
+
+    while x >= y {
+      for (dx, dy) in [(x, y), (y, x)] {
+        points.extend([
+          (cx + dx, cy + dy),
+          (cx - dx, cy + dy),
+          (cx + dx, cy - dy),
+          (cx - dx, cy - dy),
+        ]);
+      }
+
+      y += 1;
+      if error < 0 {
+        error += 2 * y + 1;
+      } else {
+        x -= 1;
+        error += 2 * (y - x) + 1;
+      }
+    }
+
+    self.blend_points(points, color);
+  }
+
+  pub fn fill_circle(&mut self, cx: i32, cy: i32, radius: i32, color: [f32; 4]) {
+    self.fill_ellipse(cx, cy, radius, radius, color);
+  }
+
+  // 중점 타원 알고리즘 (기울기가 -1인 지점을 기준으로 두 영역으로 나누어 진행)
+  pub fn draw_ellipse(&mut self, cx: i32, cy: i32, rx: i32, ry: i32, color: [f32; 4]) {
+    if rx < 0 || ry < 0 {
+      return;
+    }
+    // 반지름이 0이면 영역 1의 조건이 끝나지 않으므로 선분(또는 점)으로 그립니다.
+    if rx == 0 || ry == 0 {
+      self.draw_line(cx - rx, cy - ry, cx + rx, cy + ry, color);
+      return;
+    }
+
+    let mut points = Vec::new();
+    let mut push = |x: i32, y: i32| {
+      points.extend([
+        (cx + x, cy + y),
+        (cx - x, cy + y),
+        (cx + x, cy - y),
+        (cx - x, cy - y),
+      ]);
+    };
+
+    let (rx2, ry2) = ((rx * rx) as i64, (ry * ry) as i64);
+    let (mut x, mut y) = (0i64, ry as i64);
+
+    // 영역 1: x 방향으로 진행
+    let mut d = 4 * ry2 - 4 * rx2 * ry as i64 + rx2;
+    while ry2 * x <= rx2 * y {
+      push(x as i32, y as i32);
+      if d >= 0 {
+        y -= 1;
+        d -= 8 * rx2 * y;
+      }
+      x += 1;
+      d += 4 * ry2 * (2 * x + 1);
+    }
+
+    // 영역 2: y 방향으로 진행
+    let mut d = ry2 * (2 * x + 1) * (2 * x + 1) + 4 * rx2 * (y - 1) * (y - 1) - 4 * rx2 * ry2;
+    while y >= 0 {
+      push(x as i32, y as i32);
+      if d <= 0 {
+        x += 1;
+        d += 8 * ry2 * x;
+      }
+      y -= 1;
+      d += 4 * rx2 * (1 - 2 * y);
+    }
+
+    self.blend_points(points, color);
+  }
+
+  pub fn fill_ellipse(&mut self, cx: i32, cy: i32, rx: i32, ry: i32, color: [f32; 4]) {
+    for dy in -ry..=ry {
+      let t = dy as f32 / ry.max(1) as f32;
+      let half = (rx as f32 * (1.0 - t * t).max(0.0).sqrt()).round() as i32;
+      self.blend_span(cx - half, cx + half, cy + dy, color);
+    }
+  }
+
+  pub fn draw_rect(&mut self, x: i32, y: i32, width: i32, height: i32, color: [f32; 4]) {
+    if width <= 0 || height <= 0 {
+      return;
+    }
+
+    let (x1, y1) = (x + width - 1, y + height - 1);
+    let mut points = Vec::new();
+    for i in x..=x1 {
+      points.extend([(i, y), (i, y1)]);
+    }
+    for j in y..=y1 {
+      points.extend([(x, j), (x1, j)]);
+    }
+    self.blend_points(points, color);
+  }
+
+  pub fn fill_rect(&mut self, x: i32, y: i32, width: i32, height: i32, color: [f32; 4]) {
+    for j in y..y + height {
+      self.blend_span(x, x + width - 1, j, color);
+    }
+  }
+
+  // 닫힌 다각형의 외곽선
+  // 이웃한 변이 꼭짓점 픽셀을 나눠 덮으므로 덮인 비율을 모두 더한 뒤 픽셀마다 한 번만 블렌딩합니다.
+  pub fn draw_polygon(&mut self, points: &[(f32, f32)], color: [f32; 4]) {
+    let mut coverage: HashMap<(i32, i32), f32> = HashMap::new();
+    for (k, &(x0, y0)) in points.iter().enumerate() {
+      let (x1, y1) = points[(k + 1) % points.len()];
+      wu_line(x0, y0, x1, y1, |i, j, c| {
+        *coverage.entry((i, j)).or_default() += c;
+      });
+    }
+    for ((i, j), c) in coverage {
+      self.blend_pixel(i, j, color, c);
+    }
+  }
+
+  // 스캔라인 방식의 다각형 채우기, 픽셀 중심이 내부에 있으면 채웁니다.
+  pub fn fill_polygon(&mut self, points: &[(f32, f32)], color: [f32; 4], rule: FillRule) {
+    if points.len() < 3 {
+      return;
+    }
+
+    let y_min = points.iter().map(|p| p.1).fold(f32::MAX, f32::min);
+    let y_max = points.iter().map(|p| p.1).fold(f32::MIN, f32::max);
+    let j_min = (y_min.ceil() as i32).max(0);
+    let j_max = (y_max.ceil() as i32 - 1).min(self.height as i32 - 1);
+
+    // (교차점의 x, 변의 방향)
+    let mut crossings: Vec<(f32, i32)> = Vec::new();
+    for j in j_min..=j_max {
+      let y = j as f32;
+      crossings.clear();
+
+      for (k, &(x0, y0)) in points.iter().enumerate() {
+        let (x1, y1) = points[(k + 1) % points.len()];
+        // 위쪽 끝점은 포함하고 아래쪽 끝점은 제외해서 꼭짓점이 두 번 세어지지 않게 합니다.
+        if (y0 <= y) != (y1 <= y) {
+          let x = x0 + (y - y0) / (y1 - y0) * (x1 - x0);
+          crossings.push((x, if y1 > y0 { 1 } else { -1 }));
+        }
+      }
+      crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+      let mut winding = 0;
+      for (k, &(x, direction)) in crossings.iter().enumerate() {
+        winding += direction;
+        let inside = match rule {
+          FillRule::EvenOdd => k % 2 == 0,
+          FillRule::NonZero => winding != 0,
+        };
+
+        if inside && k + 1 < crossings.len() {
+          let x_next = crossings[k + 1].0;
+          self.blend_span(x.ceil() as i32, x_next.ceil() as i32 - 1, j, color);
+        }
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const RED: [f32; 4] = [1.0, 0.0, 0.0, 1.0];
+
+  fn count_painted(image: &Image) -> usize {
+    image.pixels.iter().filter(|p| p[3] > 0.0).count()
+  }
+
+  #[test]
+  fn test_draw_line_endpoints() {
+    let mut image = Image::new(8, 8);
+    image.draw_line(1, 6, 6, 2, RED);
+
+    assert_eq!(image.get_pixel(1, 6), &RED);
+    assert_eq!(image.get_pixel(6, 2), &RED);
+    assert_eq!(count_painted(&image), 6);
+  }
+
+  #[test]
+  fn test_draw_line_aa_coverage() {
+    let mut image = Image::new(8, 8);
+    // 두 픽셀 행 사이를 지나는 수평선은 양쪽에 절반씩 그려집니다.
+    image.draw_line_aa(1.0, 3.5, 6.0, 3.5, RED);
+
+    for i in 2..6 {
+      assert!((image.get_pixel(i, 3)[3] - 0.5).abs() < 1e-5);
+      assert!((image.get_pixel(i, 4)[3] - 0.5).abs() < 1e-5);
+    }
+    assert_eq!(image.get_pixel(3, 2)[3], 0.0);
+  }
+
+  #[test]
+  fn test_translucent_outline_blends_once() {
+    let mut image = Image::new(16, 16);
+    image.draw_circle(8, 8, 5, [1.0, 1.0, 1.0, 0.5]);
+    image.draw_ellipse(8, 8, 6, 3, [1.0, 1.0, 1.0, 0.5]);
+
+    // 두 도형이 겹치는 곳을 제외하면 모든 픽셀이 정확히 한 번만 블렌딩됩니다.
+    let values: Vec<f32> = image.pixels.iter().map(|p| p[3]).collect();
+    assert!(values.iter().all(|&a| a == 0.0 || a == 0.5 || a == 0.75));
+    assert!(values.contains(&0.5));
+  }
+
+  #[test]
+  fn test_translucent_polygon_vertices() {
+    let mut image = Image::new(8, 8);
+    let color = [1.0, 1.0, 1.0, 0.5];
+    image.draw_polygon(&[(2.0, 2.0), (5.0, 2.0), (5.0, 5.0), (2.0, 5.0)], color);
+
+    // 꼭짓점도 변의 가운데와 같은 세기로 한 번만 블렌딩됩니다.
+    for (i, j) in [(2, 2), (5, 2), (5, 5), (2, 5), (3, 2), (5, 4)] {
+      assert_eq!(image.get_pixel(i, j)[3], 0.5, "({i}, {j})");
+    }
+    assert_eq!(count_painted(&image), 12);
+  }
+
+  #[test]
+  fn test_degenerate_ellipses() {
+    let mut image = Image::new(9, 9);
+    image.draw_ellipse(4, 4, 0, 0, RED);
+    assert_eq!(count_painted(&image), 1);
+
+    let mut image = Image::new(9, 9);
+    image.draw_ellipse(4, 4, 3, 0, RED);
+    assert_eq!(count_painted(&image), 7);
+    assert!((1..8).all(|i| image.get_pixel(i, 4) == &RED));
+
+    let mut image = Image::new(9, 9);
+    image.draw_ellipse(4, 4, 0, 2, RED);
+    assert_eq!(count_painted(&image), 5);
+    assert!((2..7).all(|j| image.get_pixel(4, j) == &RED));
+
+    let mut image = Image::new(9, 9);
+    image.draw_ellipse(4, 4, -1, 2, RED);
+    assert_eq!(count_painted(&image), 0);
+  }
+
+  #[test]
+  fn test_fill_polygon_rules() {
+    // 오각별: 가운데 오각형은 nonzero 규칙에서만 채워집니다.
+    let star: Vec<(f32, f32)> = (0..5)
+      .map(|k| {
+        let angle = (k * 2) as f32 * std::f32::consts::TAU / 5.0;
+        (16.0 + 14.0 * angle.sin(), 16.0 - 14.0 * angle.cos())
+      })
+      .collect();
+
+    let mut even_odd = Image::new(32, 32);
+    even_odd.fill_polygon(&star, RED, FillRule::EvenOdd);
+    let mut non_zero = Image::new(32, 32);
+    non_zero.fill_polygon(&star, RED, FillRule::NonZero);
+
+    assert_eq!(even_odd.get_pixel(16, 17)[3], 0.0);
+    assert_eq!(non_zero.get_pixel(16, 17), &RED);
+    assert_eq!(even_odd.get_pixel(16, 5), &RED);
+    assert!(count_painted(&non_zero) > count_painted(&even_odd));
+  }
+
+  #[test]
+  fn test_fill_rect_clips_to_image() {
+    let mut image = Image::new(4, 4);
+    image.fill_rect(-2, 2, 10, 10, RED);
+    assert_eq!(count_painted(&image), 8);
+  }
+}
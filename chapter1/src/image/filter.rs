@@ -0,0 +1,144 @@
+use super::view::ImageViewMut;
+use super::{Image, GAUSSIAN_WEIGHTS};
+
+// line[center]를 중심으로 커널을 적용 (가장자리는 clamp)
+fn convolve_at(line: &[[f32; 4]], kernel: &[f32], center: i32) -> [f32; 4] {
+  let radius = (kernel.len() / 2) as i32;
+  let last = line.len() as i32 - 1;
+
+  let mut sum = [0.0; 4];
+  for (k, weight) in kernel.iter().enumerate() {
+    let pixel = line[(center + k as i32 - radius).clamp(0, last) as usize];
+    for (s, v) in sum.iter_mut().zip(pixel) {
+      *s += weight * v;
+    }
+  }
+  sum
+}
+
+// 표준편차 sigma인 정규화된 가우시안 커널 (반경은 3 sigma)
+pub fn gaussian_kernel(sigma: f32) -> Vec<f32> {
+  if sigma <= 0.0 {
+    return vec![1.0];
+  }
+
+  let radius = (3.0 * sigma).ceil() as i32;
+  let kernel: Vec<f32> = (-radius..=radius)
+    .map(|x| (-(x * x) as f32 / (2.0 * sigma * sigma)).exp())
+    .collect();
+  let sum: f32 = kernel.iter().sum();
+  kernel.iter().map(|w| w / sum).collect()
+}
+
+impl ImageViewMut<'_> {
+  // 가로 방향 1D 컨볼루션
+  pub fn convolve_rows(&mut self, kernel: &[f32]) {
+    assert!(kernel.len() % 2 == 1, "Kernel length must be odd");
+
+    let mut line = Vec::with_capacity(self.width() as usize);
+    for j in 0..self.height() {
+      line.clear();
+      line.extend_from_slice(self.row(j));
+
+      for (i, pixel) in self.row_mut(j).iter_mut().enumerate() {
+        *pixel = convolve_at(&line, kernel, i as i32);
+      }
+    }
+  }
+
+  // 세로 방향 1D 컨볼루션
+  pub fn convolve_columns(&mut self, kernel: &[f32]) {
+    assert!(kernel.len() % 2 == 1, "Kernel length must be odd");
+
+    let height = self.height() as i32;
+    let mut line = Vec::with_capacity(height as usize);
+    for i in 0..self.width() as i32 {
+      line.clear();
+      line.extend((0..height).map(|j| *self.get_pixel(i, j)));
+
+      for j in 0..height {
+        *self.get_pixel_mut(i, j) = convolve_at(&line, kernel, j);
+      }
+    }
+  }
+
+  // 분리 가능한(separable) 커널을 가로, 세로 순서로 적용
+  // 뷰 밖의 픽셀은 읽지도 쓰지도 않으므로 관심 영역(ROI)에만 필터가 적용됩니다.
+  pub fn convolve_separable(&mut self, kernel: &[f32]) {
+    if self.width() == 0 || self.height() == 0 {
+      return;
+    }
+
+    self.convolve_rows(kernel);
+    self.convolve_columns(kernel);
+  }
+
+  pub fn gaussian_blur(&mut self) {
+    self.convolve_separable(&GAUSSIAN_WEIGHTS);
+  }
+
+  pub fn gaussian_blur_sigma(&mut self, sigma: f32) {
+    self.convolve_separable(&gaussian_kernel(sigma));
+  }
+
+  pub fn box_blur(&mut self, radius: u32) {
+    let size = 2 * radius as usize + 1;
+    self.convolve_separable(&vec![1.0 / size as f32; size]);
+  }
+}
+
+impl Image {
+  pub fn gaussian_blur(&mut self) {
+    self.as_view_mut().gaussian_blur();
+  }
+
+  pub fn gaussian_blur_sigma(&mut self, sigma: f32) {
+    self.as_view_mut().gaussian_blur_sigma(sigma);
+  }
+
+  pub fn box_blur(&mut self, radius: u32) {
+    self.as_view_mut().box_blur(radius);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_blur_keeps_constant_image() {
+    let mut image = Image::new(6, 4);
+    image.as_view_mut().fill([0.25, 0.5, 0.75, 1.0]);
+    image.gaussian_blur();
+
+    for pixel in image.pixels.iter() {
+      for (v, expected) in pixel.iter().zip([0.25, 0.5, 0.75, 1.0]) {
+        assert!((v - expected).abs() < 1e-4);
+      }
+    }
+  }
+
+  #[test]
+  fn test_gaussian_kernel() {
+    let kernel = gaussian_kernel(1.5);
+    assert_eq!(kernel.len(), 11);
+    assert!((kernel.iter().sum::<f32>() - 1.0).abs() < 1e-6);
+    assert_eq!(kernel[4], kernel[6]);
+    assert_eq!(gaussian_kernel(0.0), vec![1.0]);
+  }
+
+  #[test]
+  fn test_blur_region_only() {
+    let mut image = Image::new(8, 8);
+    *image.get_pixel_mut(2, 2) = [1.0; 4];
+    *image.get_pixel_mut(6, 6) = [1.0; 4];
+
+    // 왼쪽 위 4x4 영역에만 블러 적용
+    image.view_mut(0, 0, 4, 4).box_blur(1);
+
+    assert!((image.get_pixel(2, 2)[0] - 1.0 / 9.0).abs() < 1e-6);
+    assert!((image.get_pixel(3, 3)[0] - 1.0 / 9.0).abs() < 1e-6);
+    assert_eq!(image.get_pixel(4, 4)[0], 0.0);
+    assert_eq!(image.get_pixel(6, 6)[0], 1.0);
+  }
+}
@@ -0,0 +1,331 @@
+use super::noise::Rng;
+use super::{BorderMode, Image};
+
+// Brown-Conrady 렌즈 왜곡 모델
+// 좌표는 이미지 중심이 원점이고 반 대각선 길이가 1이 되도록 정규화합니다.
+// k1 < 0이면 배럴(barrel), k1 > 0이면 핀쿠션(pincushion) 왜곡입니다.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BrownConrady {
+  pub k1: f32,
+  pub k2: f32,
+  pub k3: f32,
+  // 접선 방향(tangential) 왜곡
+  pub p1: f32,
+  pub p2: f32,
+}
+
+impl BrownConrady {
+  pub fn radial(k1: f32, k2: f32) -> Self {
+    Self {
+      k1,
+      k2,
+      ..Default::default()
+    }
+  }
+
+  // 이상적인 좌표를 렌즈를 통과한 좌표로 변환
+  pub fn distort(&self, x: f32, y: f32) -> (f32, f32) {
+    let r2 = x * x + y * y;
+    let radial = 1.0 + r2 * (self.k1 + r2 * (self.k2 + r2 * self.k3));
+    (
+      x * radial + 2.0 * self.p1 * x * y + self.p2 * (r2 + 2.0 * x * x),
+      y * radial + self.p1 * (r2 + 2.0 * y * y) + 2.0 * self.p2 * x * y,
+    )
+  }
+
+  // distort의 역변환, 닫힌 형태가 없으므로 고정점 반복으로 구합니다.
+  pub fn undistort(&self, x: f32, y: f32) -> (f32, f32) {
+    let (mut ux, mut uy) = (x, y);
+    for _ in 0..20 {
+      let r2 = ux * ux + uy * uy;
+      let radial = 1.0 + r2 * (self.k1 + r2 * (self.k2 + r2 * self.k3));
+      let dx = 2.0 * self.p1 * ux * uy + self.p2 * (r2 + 2.0 * ux * ux);
+      let dy = self.p1 * (r2 + 2.0 * uy * uy) + 2.0 * self.p2 * ux * uy;
+      ux = (x - dx) / radial;
+      uy = (y - dy) / radial;
+    }
+    (ux, uy)
+  }
+}
+
+// 순서대로 적용되는 후처리 효과
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PostEffect {
+  // radius(반 대각선 = 1)부터 softness 폭에 걸쳐 strength만큼 어두워집니다.
+  Vignette {
+    strength: f32,
+    radius: f32,
+    softness: f32,
+  },
+  // 가장자리에서 빨강은 바깥, 파랑은 안쪽으로 strength 비율만큼 밀려납니다.
+  ChromaticAberration {
+    strength: f32,
+  },
+  // 표준편차 amount인 흑백 입자, size는 입자 크기(가우시안 시그마, 픽셀)
+  FilmGrain {
+    amount: f32,
+    size: f32,
+    seed: u64,
+  },
+  // 렌즈 왜곡을 이미지에 입힙니다.
+  Distort(BrownConrady),
+  // 촬영된 이미지의 렌즈 왜곡을 보정합니다.
+  Undistort(BrownConrady),
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PostStack {
+  pub effects: Vec<PostEffect>,
+}
+
+impl PostStack {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn with(mut self, effect: PostEffect) -> Self {
+    self.effects.push(effect);
+    self
+  }
+
+  pub fn apply(&self, image: &Image) -> Image {
+    let mut output = image.clone();
+    for effect in self.effects.iter() {
+      match *effect {
+        PostEffect::Vignette {
+          strength,
+          radius,
+          softness,
+        } => output.vignette(strength, radius, softness),
+        PostEffect::ChromaticAberration { strength } => {
+          output = output.chromatic_aberration(strength)
+        }
+        PostEffect::FilmGrain { amount, size, seed } => output.film_grain(amount, size, seed),
+        PostEffect::Distort(model) => output = output.distort_lens(&model),
+        PostEffect::Undistort(model) => output = output.undistort_lens(&model),
+      }
+    }
+    output
+  }
+}
+
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+  let t = if edge1 > edge0 {
+    ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0)
+  } else if x >= edge0 {
+    1.0
+  } else {
+    0.0
+  };
+  t * t * (3.0 - 2.0 * t)
+}
+
+impl Image {
+  // 픽셀 중심 좌표를 (중심 = 0, 반 대각선 = 1)로 정규화하는 (중심, 배율)
+  fn lens_frame(&self) -> ((f32, f32), f32) {
+    let center = (
+      (self.width as f32 - 1.0) * 0.5,
+      (self.height as f32 - 1.0) * 0.5,
+    );
+    let half_diagonal = (self.width as f32).hypot(self.height as f32) * 0.5;
+    (center, half_diagonal.max(1e-6))
+  }
+
+  // 출력 픽셀의 정규화 좌표마다 원본에서 읽을 정규화 좌표를 구해서 다시 샘플링합니다.
+  fn remap(&self, f: impl Fn(f32, f32) -> (f32, f32)) -> Image {
+    let ((cx, cy), scale) = self.lens_frame();
+    let mut output = Image::new(self.width, self.height);
+    for j in 0..self.height {
+      for i in 0..self.width {
+        let (x, y) = f((i as f32 - cx) / scale, (j as f32 - cy) / scale);
+        output.pixels[(i + j * self.width) as usize] =
+          self.sample_bilinear(cx + x * scale, cy + y * scale, BorderMode::Clamp);
+      }
+    }
+    output
+  }
+
+  pub fn vignette(&mut self, strength: f32, radius: f32, softness: f32) {
+    let ((cx, cy), scale) = self.lens_frame();
+    for j in 0..self.height {
+      for i in 0..self.width {
+        let r = ((i as f32 - cx).powi(2) + (j as f32 - cy).powi(2)).sqrt() / scale;
+        let factor = 1.0 - strength * smoothstep(radius, radius + softness, r);
+        let pixel = &mut self.pixels[(i + j * self.width) as usize];
+        for v in pixel.iter_mut().take(3) {
+          *v *= factor;
+        }
+      }
+    }
+  }
+
+  // 배율 색수차: 빨강과 파랑 채널을 중심 기준으로 서로 반대 방향으로 확대/축소합니다.
+  pub fn chromatic_aberration(&self, strength: f32) -> Image {
+    // 빨강은 1 + strength배로 커지고 파랑은 같은 비율로 줄어듭니다. (strength = 1에서도 특이점이 없음)
+    assert!(
+      strength > -1.0,
+      "Chromatic aberration strength must be greater than -1"
+    );
+    let scale = 1.0 + strength;
+    let red = self.remap(|x, y| (x / scale, y / scale));
+    let blue = self.remap(|x, y| (x * scale, y * scale));
+
+    let mut output = self.clone();
+    for (k, pixel) in output.pixels.iter_mut().enumerate() {
+      pixel[0] = red.pixels[k][0];
+      pixel[2] = blue.pixels[k][2];
+    }
+    output
+  }
+
+  // 같은 seed는 같은 입자를 만들므로 애니메이션에서는 프레임마다 seed를 바꿔야 합니다.
+  pub fn film_grain(&mut self, amount: f32, size: f32, seed: u64) {
+    let mut rng = Rng::new(seed);
+    let mut grain = Image::new(self.width, self.height);
+    for pixel in grain.pixels.iter_mut() {
+      // 균등 분포 세 개의 합으로 근사한 표준 정규 분포
+      let n = (rng.next_f32() + rng.next_f32() + rng.next_f32() - 1.5) * 2.0;
+      *pixel = [n; 4];
+    }
+
+    // 블러로 입자를 키우면 분산이 줄어들므로 다시 표준편차 1로 맞춥니다.
+    if size > 0.0 {
+      grain.gaussian_blur_sigma(size);
+      let variance =
+        grain.pixels.iter().map(|p| p[0] * p[0]).sum::<f32>() / grain.pixels.len().max(1) as f32;
+      let normalize = 1.0 / variance.sqrt().max(1e-6);
+      for pixel in grain.pixels.iter_mut() {
+        pixel[0] *= normalize;
+      }
+    }
+
+    for (pixel, n) in self.pixels.iter_mut().zip(&grain.pixels) {
+      for v in pixel.iter_mut().take(3) {
+        *v += amount * n[0];
+      }
+    }
+  }
+
+  // 출력 좌표가 렌즈를 통과한 좌표이므로, 원본에서는 역변환한 좌표를 읽습니다.
+  pub fn distort_lens(&self, model: &BrownConrady) -> Image {
+    self.remap(|x, y| model.undistort(x, y))
+  }
+
+  // 출력 좌표가 보정된 좌표이므로, 원본에서는 왜곡된 좌표를 읽습니다.
+  pub fn undistort_lens(&self, model: &BrownConrady) -> Image {
+    self.remap(|x, y| model.distort(x, y))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn gray(width: u32, height: u32) -> Image {
+    let mut image = Image::new(width, height);
+    image.as_view_mut().fill([0.5, 0.5, 0.5, 1.0]);
+    image
+  }
+
+  #[test]
+  fn test_brown_conrady_inverse() {
+    let model = BrownConrady {
+      k1: -0.25,
+      k2: 0.05,
+      k3: 0.0,
+      p1: 0.01,
+      p2: -0.005,
+    };
+    for (x, y) in [(0.0, 0.0), (0.3, -0.2), (-0.6, 0.5)] {
+      let (dx, dy) = model.distort(x, y);
+      let (ux, uy) = model.undistort(dx, dy);
+      assert!((ux - x).abs() < 1e-4 && (uy - y).abs() < 1e-4);
+    }
+
+    // 배럴 왜곡은 가장자리를 안쪽으로 당깁니다.
+    let (x, _) = BrownConrady::radial(-0.2, 0.0).distort(0.7, 0.0);
+    assert!(x < 0.7);
+  }
+
+  #[test]
+  fn test_distort_then_undistort_image() {
+    let image = Image::linear_gradient(40, 30, [0.0, 0.0, 0.0, 1.0], [1.0; 4]);
+    let model = BrownConrady::radial(-0.15, 0.02);
+    let restored = image.distort_lens(&model).undistort_lens(&model);
+
+    for j in 8..22 {
+      for i in 8..32 {
+        assert!((restored.get_pixel(i, j)[0] - image.get_pixel(i, j)[0]).abs() < 0.01);
+      }
+    }
+  }
+
+  #[test]
+  fn test_vignette_and_chromatic_aberration() {
+    let mut image = gray(21, 21);
+    image.vignette(0.8, 0.3, 0.5);
+    assert_eq!(image.get_pixel(10, 10)[0], 0.5);
+    assert!(image.get_pixel(0, 0)[0] < 0.2);
+
+    // 균일한 이미지는 색수차가 보이지 않습니다.
+    let flat = gray(16, 16).chromatic_aberration(0.05);
+    assert!(flat.pixels.iter().all(|p| (p[0] - 0.5).abs() < 1e-6));
+    let strong = gray(16, 16).chromatic_aberration(1.0);
+    assert!(strong.pixels.iter().flatten().all(|v| v.is_finite()));
+
+    // 가운데의 흰 사각형 가장자리에서 빨강과 파랑이 어긋납니다.
+    let mut image = Image::new(32, 32);
+    image.fill_rect(8, 8, 16, 16, [1.0; 4]);
+    let shifted = image.chromatic_aberration(0.1);
+    let edge = shifted.get_pixel(24, 16);
+    assert!(edge[0] > edge[2]);
+  }
+
+  #[test]
+  fn test_film_grain_is_seeded() {
+    let mut a = gray(32, 32);
+    let mut b = gray(32, 32);
+    let mut c = gray(32, 32);
+    a.film_grain(0.05, 1.0, 1);
+    b.film_grain(0.05, 1.0, 1);
+    c.film_grain(0.05, 1.0, 2);
+    assert_eq!(a.pixels, b.pixels);
+    assert_ne!(a.pixels, c.pixels);
+
+    let mean = a.as_view().mean();
+    assert!((mean[0] - 0.5).abs() < 0.01);
+  }
+
+  #[test]
+  fn test_stack_applies_in_order() {
+    let image = Image::test_pattern(24, 16);
+    let stack = PostStack::new()
+      .with(PostEffect::Vignette {
+        strength: 0.5,
+        radius: 0.4,
+        softness: 0.6,
+      })
+      .with(PostEffect::FilmGrain {
+        amount: 0.02,
+        size: 0.0,
+        seed: 3,
+      });
+
+    let mut expected = image.clone();
+    expected.vignette(0.5, 0.4, 0.6);
+    expected.film_grain(0.02, 0.0, 3);
+    assert_eq!(stack.apply(&image).pixels, expected.pixels);
+
+    // 이미지를 새로 만드는 효과들도 같은 순서로 이어집니다.
+    let model = BrownConrady::radial(-0.1, 0.0);
+    let stack = PostStack::new()
+      .with(PostEffect::Distort(model))
+      .with(PostEffect::ChromaticAberration { strength: 0.05 })
+      .with(PostEffect::Undistort(model));
+    let expected = image
+      .distort_lens(&model)
+      .chromatic_aberration(0.05)
+      .undistort_lens(&model);
+    assert_eq!(stack.apply(&image).pixels, expected.pixels);
+  }
+}
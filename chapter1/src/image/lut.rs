@@ -0,0 +1,333 @@
+use super::Image;
+use glam::Vec3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LutInterpolation {
+  Trilinear,
+  // 격자 한 칸을 네 개의 사면체로 나눠서 보간 (회색 축을 따라 더 정확하고 빠름)
+  Tetrahedral,
+}
+
+// 3D 색 변환 표, .cube 파일과 같이 빨강이 가장 빠르게 변하는 순서로 저장합니다.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lut3d {
+  pub size: usize,
+  pub domain_min: [f32; 3],
+  pub domain_max: [f32; 3],
+  pub table: Vec<[f32; 3]>,
+}
+
+fn parse_floats<const N: usize>(values: &[&str], line: usize) -> [f32; N] {
+  assert!(values.len() == N, "Line {}: expected {} values", line, N);
+  std::array::from_fn(|k| {
+    values[k]
+      .parse()
+      .unwrap_or_else(|_| panic!("Line {}: invalid number {:?}", line, values[k]))
+  })
+}
+
+impl Lut3d {
+  pub fn identity(size: usize) -> Self {
+    assert!(size >= 2, "LUT size must be at least 2");
+    let scale = 1.0 / (size - 1) as f32;
+    let table = (0..size * size * size)
+      .map(|k| {
+        let (r, g, b) = (k % size, k / size % size, k / (size * size));
+        [r as f32 * scale, g as f32 * scale, b as f32 * scale]
+      })
+      .collect();
+
+    Self {
+      size,
+      domain_min: [0.0; 3],
+      domain_max: [1.0; 3],
+      table,
+    }
+  }
+
+  // Adobe/Resolve .cube 형식 (LUT_3D_SIZE, DOMAIN_MIN, DOMAIN_MAX, TITLE, # 주석)
+  pub fn parse_cube(text: &str) -> Self {
+    let mut size = None;
+    let mut domain_min = [0.0; 3];
+    let mut domain_max = [1.0; 3];
+    let mut table = Vec::new();
+
+    for (index, line) in text.lines().enumerate() {
+      let line_number = index + 1;
+      let line = line.split('#').next().unwrap().trim();
+      let mut parts = line.split_whitespace();
+      let Some(keyword) = parts.next() else {
+        continue;
+      };
+      let values: Vec<&str> = parts.collect();
+
+      match keyword {
+        "TITLE" => {}
+        "LUT_3D_SIZE" => {
+          let [n] = parse_floats::<1>(&values, line_number);
+          size = Some(n as usize);
+        }
+        "LUT_1D_SIZE" => panic!("Line {}: 1D LUTs are not supported", line_number),
+        "DOMAIN_MIN" => domain_min = parse_floats(&values, line_number),
+        "DOMAIN_MAX" => domain_max = parse_floats(&values, line_number),
+        "LUT_3D_INPUT_RANGE" => {
+          let [min, max] = parse_floats::<2>(&values, line_number);
+          domain_min = [min; 3];
+          domain_max = [max; 3];
+        }
+        _ => {
+          let mut entry = vec![keyword];
+          entry.extend(values);
+          table.push(parse_floats::<3>(&entry, line_number));
+        }
+      }
+    }
+
+    let size = size.expect("Missing LUT_3D_SIZE");
+    assert!(size >= 2, "LUT size must be at least 2");
+    assert!(
+      (0..3).all(|c| domain_min[c] < domain_max[c]),
+      "DOMAIN_MAX must be greater than DOMAIN_MIN"
+    );
+    assert_eq!(
+      table.len(),
+      size * size * size,
+      "Expected {} LUT entries",
+      size * size * size
+    );
+
+    Self {
+      size,
+      domain_min,
+      domain_max,
+      table,
+    }
+  }
+
+  pub fn to_cube_string(&self) -> String {
+    let mut text = format!("LUT_3D_SIZE {}\n", self.size);
+    if self.domain_min != [0.0; 3] || self.domain_max != [1.0; 3] {
+      let [r0, g0, b0] = self.domain_min;
+      let [r1, g1, b1] = self.domain_max;
+      text += &format!(
+        "DOMAIN_MIN {} {} {}\nDOMAIN_MAX {} {} {}\n",
+        r0, g0, b0, r1, g1, b1
+      );
+    }
+    for [r, g, b] in self.table.iter() {
+      text += &format!("{:.6} {:.6} {:.6}\n", r, g, b);
+    }
+    text
+  }
+
+  #[allow(dead_code)]
+  pub fn read_cube(filename: &str) -> Self {
+    let text = std::fs::read_to_string(filename).expect("Failed to open file");
+    Self::parse_cube(&text)
+  }
+
+  #[allow(dead_code)]
+  pub fn write_cube(&self, filename: &str) {
+    std::fs::write(filename, self.to_cube_string()).expect("Failed to save CUBE file");
+  }
+
+  fn entry(&self, r: usize, g: usize, b: usize) -> [f32; 3] {
+    self.table[r + (g + b * self.size) * self.size]
+  }
+
+  pub fn sample(&self, rgb: [f32; 3], interpolation: LutInterpolation) -> [f32; 3] {
+    let last = (self.size - 1) as f32;
+
+    // 격자 좌표와 격자 안에서의 위치
+    let mut base = [0; 3];
+    let mut f = [0.0; 3];
+    for c in 0..3 {
+      // 필드를 직접 채운 LUT의 범위가 비어 있으면 격자의 첫 칸을 사용합니다.
+      let range = self.domain_max[c] - self.domain_min[c];
+      let t = if range > 0.0 {
+        (rgb[c] - self.domain_min[c]) / range
+      } else {
+        0.0
+      };
+      let x = (t * last).clamp(0.0, last);
+      base[c] = (x.floor() as usize).min(self.size - 2);
+      f[c] = x - base[c] as f32;
+    }
+
+    let corner = |dr: usize, dg: usize, db: usize| {
+      Vec3::from(self.entry(base[0] + dr, base[1] + dg, base[2] + db))
+    };
+    let [fr, fg, fb] = f;
+
+    let result = match interpolation {
+      LutInterpolation::Trilinear => {
+        let lerp = |a: Vec3, b: Vec3, t: f32| a + (b - a) * t;
+        let c00 = lerp(corner(0, 0, 0), corner(1, 0, 0), fr);
+        let c10 = lerp(corner(0, 1, 0), corner(1, 1, 0), fr);
+        let c01 = lerp(corner(0, 0, 1), corner(1, 0, 1), fr);
+        let c11 = lerp(corner(0, 1, 1), corner(1, 1, 1), fr);
+        lerp(lerp(c00, c10, fg), lerp(c01, c11, fg), fb)
+      }
+      LutInterpolation::Tetrahedral => {
+        let (c000, c111) = (corner(0, 0, 0), corner(1, 1, 1));
+        if fr > fg {
+          if fg > fb {
+            let (c100, c110) = (corner(1, 0, 0), corner(1, 1, 0));
+            c000 + fr * (c100 - c000) + fg * (c110 - c100) + fb * (c111 - c110)
+          } else if fr > fb {
+            let (c100, c101) = (corner(1, 0, 0), corner(1, 0, 1));
+            c000 + fr * (c100 - c000) + fb * (c101 - c100) + fg * (c111 - c101)
+          } else {
+            let (c001, c101) = (corner(0, 0, 1), corner(1, 0, 1));
+            c000 + fb * (c001 - c000) + fr * (c101 - c001) + fg * (c111 - c101)
+          }
+        } else if fb > fg {
+          let (c001, c011) = (corner(0, 0, 1), corner(0, 1, 1));
+          c000 + fb * (c001 - c000) + fg * (c011 - c001) + fr * (c111 - c011)
+        } else if fb > fr {
+          let (c010, c011) = (corner(0, 1, 0), corner(0, 1, 1));
+          c000 + fg * (c010 - c000) + fb * (c011 - c010) + fr * (c111 - c011)
+        } else {
+          let (c010, c110) = (corner(0, 1, 0), corner(1, 1, 0));
+          c000 + fg * (c010 - c000) + fr * (c110 - c010) + fb * (c111 - c110)
+        }
+      }
+    };
+    result.to_array()
+  }
+
+  // 보정된 항등 LUT 스트립(Image::lut_identity_strip 참고)을 다시 LUT로 읽습니다.
+  pub fn from_strip(strip: &Image) -> Self {
+    let size = strip.height as usize;
+    assert!(
+      size >= 2 && strip.width as usize == size * size,
+      "LUT strip must be size^2 x size"
+    );
+
+    let table = (0..size * size * size)
+      .map(|k| {
+        let (r, g, b) = (k % size, k / size % size, k / (size * size));
+        let p = strip.pixels[r + b * size + g * strip.width as usize];
+        [p[0], p[1], p[2]]
+      })
+      .collect();
+
+    Self {
+      size,
+      domain_min: [0.0; 3],
+      domain_max: [1.0; 3],
+      table,
+    }
+  }
+}
+
+impl Image {
+  pub fn apply_lut(&mut self, lut: &Lut3d, interpolation: LutInterpolation) {
+    for pixel in self.pixels.iter_mut() {
+      let [r, g, b] = lut.sample([pixel[0], pixel[1], pixel[2]], interpolation);
+      *pixel = [r, g, b, pixel[3]];
+    }
+  }
+
+  // 가로로 size개의 size x size 타일을 이어 붙인 항등 LUT 스트립
+  // 타일 안에서 가로가 빨강, 세로가 초록이고 타일 순서가 파랑입니다.
+  // 외부 도구에서 이 이미지를 보정한 뒤 Lut3d::from_strip으로 구워 넣습니다.
+  pub fn lut_identity_strip(size: u32) -> Image {
+    let lut = Lut3d::identity(size as usize);
+    let mut image = Image::new(size * size, size);
+    for (k, [r, g, b]) in lut.table.iter().enumerate() {
+      let k = k as u32;
+      let (i, j, tile) = (k % size, k / size % size, k / (size * size));
+      *image.get_pixel_mut((i + tile * size) as i32, j as i32) = [*r, *g, *b, 1.0];
+    }
+    image
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::image::noise::Rng;
+
+  const INVERT_CUBE: &str = "
+    # 색 반전
+    TITLE \"invert\"
+    LUT_3D_SIZE 2
+
+    1 1 1
+    0 1 1
+    1 0 1
+    0 0 1
+    1 1 0
+    0 1 0
+    1 0 0
+    0 0 0
+  ";
+
+  fn random_image() -> Image {
+    let mut rng = Rng::new(7);
+    let mut image = Image::new(8, 8);
+    for pixel in image.pixels.iter_mut() {
+      *pixel = [rng.next_f32(), rng.next_f32(), rng.next_f32(), 0.5];
+    }
+    image
+  }
+
+  #[test]
+  fn test_identity_lut_preserves_colors() {
+    let original = random_image();
+    for interpolation in [LutInterpolation::Trilinear, LutInterpolation::Tetrahedral] {
+      let mut image = original.clone();
+      image.apply_lut(&Lut3d::identity(5), interpolation);
+      for (a, b) in image.pixels.iter().zip(&original.pixels) {
+        assert!(a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-5));
+      }
+    }
+  }
+
+  #[test]
+  fn test_parse_and_write_cube() {
+    let lut = Lut3d::parse_cube(INVERT_CUBE);
+    assert_eq!(lut.size, 2);
+
+    // 선형 변환은 두 보간 방식 모두 정확합니다.
+    for interpolation in [LutInterpolation::Trilinear, LutInterpolation::Tetrahedral] {
+      let [r, g, b] = lut.sample([0.2, 0.7, 0.4], interpolation);
+      assert!((r - 0.8).abs() < 1e-5 && (g - 0.3).abs() < 1e-5 && (b - 0.6).abs() < 1e-5);
+    }
+
+    assert_eq!(Lut3d::parse_cube(&lut.to_cube_string()), lut);
+  }
+
+  #[test]
+  #[should_panic(expected = "DOMAIN_MAX must be greater than DOMAIN_MIN")]
+  fn test_degenerate_domain() {
+    Lut3d::parse_cube("LUT_3D_SIZE 2\nDOMAIN_MIN 0 0 0\nDOMAIN_MAX 1 0 1\n");
+  }
+
+  #[test]
+  fn test_degenerate_domain_samples_first_cell() {
+    let mut lut = Lut3d::identity(4);
+    lut.domain_max[1] = 0.0;
+    let [r, g, b] = lut.sample([0.5, 0.5, 0.5], LutInterpolation::Trilinear);
+    assert!((r - 0.5).abs() < 1e-5 && g == 0.0 && (b - 0.5).abs() < 1e-5);
+  }
+
+  #[test]
+  fn test_strip_round_trip() {
+    let strip = Image::lut_identity_strip(4);
+    assert_eq!((strip.width, strip.height), (16, 4));
+    assert_eq!(strip.get_pixel(15, 3), &[1.0; 4]);
+    assert_eq!(Lut3d::from_strip(&strip), Lut3d::identity(4));
+
+    // 스트립을 반전해서 구운 LUT는 .cube 반전 LUT와 같은 결과를 냅니다.
+    let mut graded = strip.clone();
+    graded.apply_lut(
+      &Lut3d::parse_cube(INVERT_CUBE),
+      LutInterpolation::Tetrahedral,
+    );
+    let baked = Lut3d::from_strip(&graded);
+    let [r, g, b] = baked.sample([0.2, 0.7, 0.4], LutInterpolation::Tetrahedral);
+    assert!((r - 0.8).abs() < 1e-5 && (g - 0.3).abs() < 1e-5 && (b - 0.6).abs() < 1e-5);
+  }
+}
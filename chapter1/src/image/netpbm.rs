@@ -0,0 +1,384 @@
+use super::{luminance, to_u8, Image};
+
+// PPM/PGM 데이터를 텍스트(P3/P2)로 쓸지 바이너리(P6/P5)로 쓸지 선택
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NetpbmEncoding {
+  Ascii,
+  Binary,
+}
+
+// 헤더에서 읽은 크기들의 곱, 넘치면 파일이 잘못된 것이므로 message로 패닉합니다.
+fn checked_size(factors: &[usize], message: &str) -> usize {
+  factors
+    .iter()
+    .try_fold(1usize, |acc, &f| acc.checked_mul(f))
+    .unwrap_or_else(|| panic!("{}", message))
+}
+
+// Netpbm 헤더를 읽기 위한 토크나이저 ('#'부터 줄 끝까지는 주석)
+struct HeaderReader<'a> {
+  bytes: &'a [u8],
+  pos: usize,
+}
+
+impl<'a> HeaderReader<'a> {
+  fn skip_whitespace_and_comments(&mut self) {
+    while self.pos < self.bytes.len() {
+      match self.bytes[self.pos] {
+        b'#' => {
+          while self.pos < self.bytes.len() && self.bytes[self.pos] != b'\n' {
+            self.pos += 1;
+          }
+        }
+        c if c.is_ascii_whitespace() => self.pos += 1,
+        _ => break,
+      }
+    }
+  }
+
+  fn token(&mut self) -> &'a str {
+    self.skip_whitespace_and_comments();
+    let start = self.pos;
+    while self.pos < self.bytes.len() && !self.bytes[self.pos].is_ascii_whitespace() {
+      self.pos += 1;
+    }
+    std::str::from_utf8(&self.bytes[start..self.pos]).expect("Invalid header token")
+  }
+
+  fn number<T: std::str::FromStr>(&mut self) -> T {
+    let token = self.token();
+    token
+      .parse()
+      .unwrap_or_else(|_| panic!("Invalid number in header: {:?}", token))
+  }
+
+  // 헤더 마지막 값 뒤의 공백 한 글자를 건너뛰고 바이너리 데이터를 반환
+  fn binary_data(&mut self) -> &'a [u8] {
+    &self.bytes[(self.pos + 1).min(self.bytes.len())..]
+  }
+}
+
+impl Image {
+  pub fn encode_ppm(&self, encoding: NetpbmEncoding) -> Vec<u8> {
+    let magic = match encoding {
+      NetpbmEncoding::Ascii => "P3",
+      NetpbmEncoding::Binary => "P6",
+    };
+    let mut bytes = format!("{}\n{} {}\n255\n", magic, self.width, self.height).into_bytes();
+
+    for row in self.pixels.chunks(self.width.max(1) as usize) {
+      let values = row.iter().flat_map(|p| [p[0], p[1], p[2]].map(to_u8));
+      match encoding {
+        NetpbmEncoding::Ascii => {
+          let line: Vec<String> = values.map(|v| v.to_string()).collect();
+          bytes.extend(line.join(" ").bytes());
+          bytes.push(b'\n');
+        }
+        NetpbmEncoding::Binary => bytes.extend(values),
+      }
+    }
+    bytes
+  }
+
+  // 회색조는 Rec.709 휘도(luminance)로 저장
+  pub fn encode_pgm(&self, encoding: NetpbmEncoding) -> Vec<u8> {
+    let magic = match encoding {
+      NetpbmEncoding::Ascii => "P2",
+      NetpbmEncoding::Binary => "P5",
+    };
+    let mut bytes = format!("{}\n{} {}\n255\n", magic, self.width, self.height).into_bytes();
+
+    for row in self.pixels.chunks(self.width.max(1) as usize) {
+      let values = row.iter().map(|p| to_u8(luminance(p)));
+      match encoding {
+        NetpbmEncoding::Ascii => {
+          let line: Vec<String> = values.map(|v| v.to_string()).collect();
+          bytes.extend(line.join(" ").bytes());
+          bytes.push(b'\n');
+        }
+        NetpbmEncoding::Binary => bytes.extend(values),
+      }
+    }
+    bytes
+  }
+
+  // P2, P3, P5, P6 형식을 모두 읽습니다. (maxval이 255보다 크면 16비트 빅엔디언)
+  pub fn decode_netpbm(bytes: &[u8]) -> Self {
+    let mut header = HeaderReader { bytes, pos: 0 };
+    let magic = header.token();
+    let (channels, ascii) = match magic {
+      "P2" => (1, true),
+      "P3" => (3, true),
+      "P5" => (1, false),
+      "P6" => (3, false),
+      _ => panic!("Unsupported Netpbm format: {:?}", magic),
+    };
+
+    let width: u32 = header.number();
+    let height: u32 = header.number();
+    let max_value: u32 = header.number();
+    assert!(
+      (1..=65535).contains(&max_value),
+      "Invalid maxval: {}",
+      max_value
+    );
+
+    let count = checked_size(
+      &[width as usize, height as usize, channels as usize],
+      "Truncated Netpbm data",
+    );
+    let samples: Vec<u32> = if ascii {
+      // 값마다 적어도 한 바이트가 필요하므로 미리 걸러서 큰 할당을 막습니다.
+      assert!(count <= bytes.len(), "Truncated Netpbm data");
+      (0..count).map(|_| header.number()).collect()
+    } else {
+      let data = header.binary_data();
+      let sample_size = if max_value > 255 { 2 } else { 1 };
+      let size = checked_size(&[count, sample_size], "Truncated Netpbm data");
+      assert!(data.len() >= size, "Truncated Netpbm data");
+
+      data
+        .chunks_exact(sample_size)
+        .take(count)
+        .map(|s| s.iter().fold(0, |acc, &b| (acc << 8) | b as u32))
+        .collect()
+    };
+
+    let scale = 1.0 / max_value as f32;
+    let pixels = samples
+      .chunks_exact(channels as usize)
+      .map(|s| match s {
+        [v] => [*v as f32 * scale, *v as f32 * scale, *v as f32 * scale, 1.0],
+        [r, g, b] => [*r as f32 * scale, *g as f32 * scale, *b as f32 * scale, 1.0],
+        _ => unreachable!(),
+      })
+      .collect();
+
+    Self {
+      width,
+      height,
+      channels: channels as u8,
+      pixels,
+    }
+  }
+
+  // PFM은 아래 행부터 저장하며, 음수 scale은 리틀엔디언을 뜻합니다.
+  // 회색조 이미지(channels == 1)는 "Pf", 나머지는 "PF"로 저장합니다.
+  pub fn encode_pfm(&self) -> Vec<u8> {
+    let gray = self.channels == 1;
+    let magic = if gray { "Pf" } else { "PF" };
+    let mut bytes = format!("{}\n{} {}\n-1.0\n", magic, self.width, self.height).into_bytes();
+
+    for row in self.pixels.chunks(self.width.max(1) as usize).rev() {
+      for pixel in row {
+        let values: &[f32] = if gray { &pixel[..1] } else { &pixel[..3] };
+        for value in values {
+          bytes.extend(value.to_le_bytes());
+        }
+      }
+    }
+    bytes
+  }
+
+  pub fn decode_pfm(bytes: &[u8]) -> Self {
+    let mut header = HeaderReader { bytes, pos: 0 };
+    let channels = match header.token() {
+      "Pf" => 1,
+      "PF" => 3,
+      magic => panic!("Unsupported PFM format: {:?}", magic),
+    };
+
+    let width: u32 = header.number();
+    let height: u32 = header.number();
+    let scale: f32 = header.number();
+    let little_endian = scale < 0.0;
+
+    let data = header.binary_data();
+    let row_len = checked_size(&[width as usize, channels], "Truncated PFM data");
+    let count = checked_size(&[row_len, height as usize], "Truncated PFM data");
+    assert!(data.len() / 4 >= count, "Truncated PFM data");
+
+    let values: Vec<f32> = data
+      .chunks_exact(4)
+      .take(count)
+      .map(|b| {
+        let b = [b[0], b[1], b[2], b[3]];
+        if little_endian {
+          f32::from_le_bytes(b)
+        } else {
+          f32::from_be_bytes(b)
+        }
+      })
+      .collect();
+
+    // 아래 행부터 저장되어 있으므로 행 순서를 뒤집습니다.
+    let mut pixels = Vec::with_capacity(count / channels);
+    for row in values.chunks(row_len.max(1)).rev() {
+      for s in row.chunks_exact(channels) {
+        pixels.push(match s {
+          [v] => [*v, *v, *v, 1.0],
+          [r, g, b] => [*r, *g, *b, 1.0],
+          _ => unreachable!(),
+        });
+      }
+    }
+
+    Self {
+      width,
+      height,
+      channels: channels as u8,
+      pixels,
+    }
+  }
+
+  // 헤더 없이 RGBA f32 값을 리틀엔디언으로 나열 (행 우선)
+  pub fn encode_raw_f32(&self) -> Vec<u8> {
+    self
+      .pixels
+      .iter()
+      .flat_map(|p| p.iter().flat_map(|v| v.to_le_bytes()))
+      .collect()
+  }
+
+  pub fn decode_raw_f32(bytes: &[u8], width: u32, height: u32) -> Self {
+    let count = checked_size(
+      &[width as usize, height as usize, 16],
+      "Raw f32 dump size mismatch",
+    );
+    assert_eq!(bytes.len(), count, "Raw f32 dump size mismatch");
+
+    let pixels = bytes
+      .chunks_exact(16)
+      .map(|p| {
+        std::array::from_fn(|c| {
+          f32::from_le_bytes([p[c * 4], p[c * 4 + 1], p[c * 4 + 2], p[c * 4 + 3]])
+        })
+      })
+      .collect();
+
+    Self {
+      width,
+      height,
+      channels: 4,
+      pixels,
+    }
+  }
+
+  #[allow(dead_code)]
+  pub fn write_ppm(&self, filename: &str, encoding: NetpbmEncoding) {
+    std::fs::write(filename, self.encode_ppm(encoding)).expect("Failed to save PPM file");
+  }
+
+  #[allow(dead_code)]
+  pub fn write_pgm(&self, filename: &str, encoding: NetpbmEncoding) {
+    std::fs::write(filename, self.encode_pgm(encoding)).expect("Failed to save PGM file");
+  }
+
+  #[allow(dead_code)]
+  pub fn read_netpbm(filename: &str) -> Self {
+    Self::decode_netpbm(&std::fs::read(filename).expect("Failed to open file"))
+  }
+
+  #[allow(dead_code)]
+  pub fn write_pfm(&self, filename: &str) {
+    std::fs::write(filename, self.encode_pfm()).expect("Failed to save PFM file");
+  }
+
+  #[allow(dead_code)]
+  pub fn read_pfm(filename: &str) -> Self {
+    Self::decode_pfm(&std::fs::read(filename).expect("Failed to open file"))
+  }
+
+  #[allow(dead_code)]
+  pub fn write_raw_f32(&self, filename: &str) {
+    std::fs::write(filename, self.encode_raw_f32()).expect("Failed to save raw dump");
+  }
+
+  #[allow(dead_code)]
+  pub fn read_raw_f32(filename: &str, width: u32, height: u32) -> Self {
+    let bytes = std::fs::read(filename).expect("Failed to open file");
+    Self::decode_raw_f32(&bytes, width, height)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sample_image() -> Image {
+    let mut image = Image::new(3, 2);
+    for (k, pixel) in image.pixels.iter_mut().enumerate() {
+      let v = k as f32 / 5.0;
+      *pixel = [v, 1.0 - v, 0.5, 1.0];
+    }
+    image
+  }
+
+  #[test]
+  fn test_ppm_round_trip() {
+    let image = sample_image();
+    for encoding in [NetpbmEncoding::Ascii, NetpbmEncoding::Binary] {
+      let decoded = Image::decode_netpbm(&image.encode_ppm(encoding));
+      assert_eq!((decoded.width, decoded.height, decoded.channels), (3, 2, 3));
+
+      for (a, b) in image.pixels.iter().zip(&decoded.pixels) {
+        for c in 0..4 {
+          assert!((a[c] - b[c]).abs() <= 0.5 / 255.0 + 1e-6);
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn test_ascii_ppm_is_diffable() {
+    let mut image = Image::new(2, 1);
+    image.pixels = vec![[1.0, 0.0, 0.0, 1.0], [0.0, 0.0, 1.0, 1.0]];
+    let text = String::from_utf8(image.encode_ppm(NetpbmEncoding::Ascii)).unwrap();
+    assert_eq!(text, "P3\n2 1\n255\n255 0 0 0 0 255\n");
+
+    let gray = String::from_utf8(image.encode_pgm(NetpbmEncoding::Ascii)).unwrap();
+    assert_eq!(gray, "P2\n2 1\n255\n54 18\n");
+  }
+
+  #[test]
+  fn test_decode_netpbm_with_comments_and_16_bit() {
+    let mut bytes = b"P5 # gray\n2 1\n# max\n65535\n".to_vec();
+    bytes.extend([0xff, 0xff, 0x80, 0x00]);
+    let image = Image::decode_netpbm(&bytes);
+
+    assert_eq!(image.channels, 1);
+    assert_eq!(image.pixels[0], [1.0, 1.0, 1.0, 1.0]);
+    assert!((image.pixels[1][0] - 0x8000 as f32 / 65535.0).abs() < 1e-6);
+  }
+
+  #[test]
+  fn test_pfm_keeps_full_precision() {
+    let mut image = sample_image();
+    image.pixels[0] = [1.0e-7, 123.456, -2.5, 1.0];
+    let decoded = Image::decode_pfm(&image.encode_pfm());
+
+    assert_eq!(decoded.pixels, image.pixels);
+
+    // 빅엔디언(양수 scale)도 읽을 수 있어야 합니다.
+    let mut big_endian = b"Pf\n1 1\n1.0\n".to_vec();
+    big_endian.extend(0.25f32.to_be_bytes());
+    assert_eq!(
+      Image::decode_pfm(&big_endian).pixels[0],
+      [0.25, 0.25, 0.25, 1.0]
+    );
+  }
+
+  #[test]
+  fn test_raw_f32_round_trip() {
+    let image = sample_image();
+    let bytes = image.encode_raw_f32();
+    assert_eq!(bytes.len(), 3 * 2 * 16);
+    assert_eq!(Image::decode_raw_f32(&bytes, 3, 2).pixels, image.pixels);
+  }
+
+  #[test]
+  #[should_panic(expected = "Truncated Netpbm data")]
+  fn test_oversized_header_is_rejected() {
+    Image::decode_netpbm(b"P6\n4294967295 4294967295\n255\n\x00");
+  }
+}
@@ -0,0 +1,403 @@
+use super::Image;
+use std::f32::consts::{FRAC_1_SQRT_2, SQRT_2, TAU};
+
+// 시드 기반 의사 난수 생성기 (SplitMix64)
+// 테스트와 데모에서 같은 시드로 항상 같은 결과를 얻기 위해 사용합니다.
+#[derive(Clone, Debug)]
+pub struct Rng {
+  state: u64,
+}
+
+impl Rng {
+  pub fn new(seed: u64) -> Self {
+    Self { state: seed }
+  }
+
+  pub fn next_u64(&mut self) -> u64 {
+    self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = self.state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+  }
+
+  // [0, 1) 범위의 실수
+  pub fn next_f32(&mut self) -> f32 {
+    (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+  }
+
+  // [0, n) 범위의 정수
+  pub fn below(&mut self, n: u32) -> u32 {
+    (((self.next_u64() >> 32) * n as u64) >> 32) as u32
+  }
+}
+
+// 격자 좌표를 섞어서 의사 난수 해시를 만듭니다.
+fn hash(seed: u32, coords: &[i32]) -> u32 {
+  let mut h = seed.wrapping_mul(0x9e37_79b9);
+  for &c in coords {
+    h ^= (c as u32).wrapping_mul(0x27d4_eb2d);
+    h = h.rotate_left(13).wrapping_mul(0x85eb_ca6b);
+  }
+  h ^= h >> 16;
+  h = h.wrapping_mul(0x7feb_352d);
+  h ^= h >> 15;
+  h = h.wrapping_mul(0x846c_a68b);
+  h ^ (h >> 16)
+}
+
+fn hash_f32(seed: u32, coords: &[i32]) -> f32 {
+  (hash(seed, coords) >> 8) as f32 / (1u32 << 24) as f32
+}
+
+fn fade(t: f32) -> f32 {
+  t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+  a + (b - a) * t
+}
+
+const GRAD2: [[f32; 2]; 8] = [
+  [1.0, 0.0],
+  [-1.0, 0.0],
+  [0.0, 1.0],
+  [0.0, -1.0],
+  [FRAC_1_SQRT_2, FRAC_1_SQRT_2],
+  [-FRAC_1_SQRT_2, FRAC_1_SQRT_2],
+  [FRAC_1_SQRT_2, -FRAC_1_SQRT_2],
+  [-FRAC_1_SQRT_2, -FRAC_1_SQRT_2],
+];
+
+// (0, ±1, ±1, ±1)의 모든 순열
+const GRAD4: [[f32; 4]; 32] = [
+  [0.0, 1.0, 1.0, 1.0],
+  [0.0, 1.0, 1.0, -1.0],
+  [0.0, 1.0, -1.0, 1.0],
+  [0.0, 1.0, -1.0, -1.0],
+  [0.0, -1.0, 1.0, 1.0],
+  [0.0, -1.0, 1.0, -1.0],
+  [0.0, -1.0, -1.0, 1.0],
+  [0.0, -1.0, -1.0, -1.0],
+  [1.0, 0.0, 1.0, 1.0],
+  [1.0, 0.0, 1.0, -1.0],
+  [1.0, 0.0, -1.0, 1.0],
+  [1.0, 0.0, -1.0, -1.0],
+  [-1.0, 0.0, 1.0, 1.0],
+  [-1.0, 0.0, 1.0, -1.0],
+  [-1.0, 0.0, -1.0, 1.0],
+  [-1.0, 0.0, -1.0, -1.0],
+  [1.0, 1.0, 0.0, 1.0],
+  [1.0, 1.0, 0.0, -1.0],
+  [1.0, -1.0, 0.0, 1.0],
+  [1.0, -1.0, 0.0, -1.0],
+  [-1.0, 1.0, 0.0, 1.0],
+  [-1.0, 1.0, 0.0, -1.0],
+  [-1.0, -1.0, 0.0, 1.0],
+  [-1.0, -1.0, 0.0, -1.0],
+  [1.0, 1.0, 1.0, 0.0],
+  [1.0, 1.0, -1.0, 0.0],
+  [1.0, -1.0, 1.0, 0.0],
+  [1.0, -1.0, -1.0, 0.0],
+  [-1.0, 1.0, 1.0, 0.0],
+  [-1.0, 1.0, -1.0, 0.0],
+  [-1.0, -1.0, 1.0, 0.0],
+  [-1.0, -1.0, -1.0, 0.0],
+];
+
+// 격자 주기(period)로 감싸서 경계가 이어지는 Perlin 노이즈, 결과는 약 [-1, 1]
+pub fn perlin(seed: u32, x: f32, y: f32, period: u32) -> f32 {
+  let period = period.max(1) as i32;
+  let (xf, yf) = (x.floor(), y.floor());
+  let (ix, iy) = (xf as i32, yf as i32);
+  let (fx, fy) = (x - xf, y - yf);
+
+  let gradient = |cx: i32, cy: i32, dx: f32, dy: f32| {
+    let h = hash(seed, &[cx.rem_euclid(period), cy.rem_euclid(period)]);
+    let g = GRAD2[(h & 7) as usize];
+    g[0] * dx + g[1] * dy
+  };
+
+  let n00 = gradient(ix, iy, fx, fy);
+  let n10 = gradient(ix + 1, iy, fx - 1.0, fy);
+  let n01 = gradient(ix, iy + 1, fx, fy - 1.0);
+  let n11 = gradient(ix + 1, iy + 1, fx - 1.0, fy - 1.0);
+
+  let (u, v) = (fade(fx), fade(fy));
+  lerp(lerp(n00, n10, u), lerp(n01, n11, u), v) * SQRT_2
+}
+
+// 4D 심플렉스 노이즈, 결과는 약 [-1, 1]
+pub fn simplex4(seed: u32, p: [f32; 4]) -> f32 {
+  let f4 = (5.0f32.sqrt() - 1.0) / 4.0;
+  let g4 = (5.0 - 5.0f32.sqrt()) / 20.0;
+
+  // 입력 공간을 기울여서(skew) 어느 하이퍼큐브 셀에 속하는지 찾기
+  let s = p.iter().sum::<f32>() * f4;
+  let cell = p.map(|c| (c + s).floor());
+  let t = cell.iter().sum::<f32>() * g4;
+  let x0: [f32; 4] = std::array::from_fn(|k| p[k] - (cell[k] - t));
+
+  // 좌표의 크기 순서로 심플렉스의 꼭짓점 순서를 결정
+  let mut rank = [0; 4];
+  for a in 0..4 {
+    for b in a + 1..4 {
+      if x0[a] > x0[b] {
+        rank[a] += 1;
+      } else {
+        rank[b] += 1;
+      }
+    }
+  }
+
+  let base = cell.map(|c| c as i32);
+  let mut sum = 0.0;
+  for corner in 0..5 {
+    // corner번째 꼭짓점의 격자 오프셋
+    let offset: [i32; 4] = std::array::from_fn(|k| (rank[k] >= 4 - corner) as i32);
+    let d: [f32; 4] = std::array::from_fn(|k| x0[k] - offset[k] as f32 + corner as f32 * g4);
+
+    let falloff = 0.6 - d.iter().map(|v| v * v).sum::<f32>();
+    if falloff > 0.0 {
+      let coords: [i32; 4] = std::array::from_fn(|k| base[k] + offset[k]);
+      let g = GRAD4[(hash(seed, &coords) & 31) as usize];
+      let dot = g.iter().zip(d).map(|(a, b)| a * b).sum::<f32>();
+      sum += falloff.powi(4) * dot;
+    }
+  }
+
+  27.0 * sum
+}
+
+// [0, 1) 범위의 텍스처 좌표 (u, v)를 토러스 위의 4D 점으로 보내서 경계가 이어지는 심플렉스 노이즈
+pub fn simplex_tileable(seed: u32, u: f32, v: f32, period: u32) -> f32 {
+  let radius = period.max(1) as f32 / TAU;
+  let (su, cu) = (u * TAU).sin_cos();
+  let (sv, cv) = (v * TAU).sin_cos();
+  simplex4(seed, [radius * cu, radius * su, radius * cv, radius * sv])
+}
+
+// 가장 가까운 특징점까지의 거리(F1)를 사용하는 Worley(셀룰러) 노이즈, 결과는 [0, 1]
+pub fn worley(seed: u32, x: f32, y: f32, period: u32) -> f32 {
+  let period = period.max(1) as i32;
+  let (ix, iy) = (x.floor() as i32, y.floor() as i32);
+
+  let mut nearest = f32::MAX;
+  for cy in iy - 1..=iy + 1 {
+    for cx in ix - 1..=ix + 1 {
+      let (wx, wy) = (cx.rem_euclid(period), cy.rem_euclid(period));
+      let px = cx as f32 + hash_f32(seed, &[wx, wy, 0]);
+      let py = cy as f32 + hash_f32(seed, &[wx, wy, 1]);
+      nearest = nearest.min((px - x).powi(2) + (py - y).powi(2));
+    }
+  }
+
+  nearest.sqrt().min(1.0)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Noise {
+  Perlin,
+  Simplex,
+  Worley,
+}
+
+impl Noise {
+  // 텍스처 좌표 (u, v)에서 주기 period로 샘플링, 결과는 [-1, 1]
+  pub fn sample(&self, seed: u32, u: f32, v: f32, period: u32) -> f32 {
+    let p = period as f32;
+    match self {
+      Noise::Perlin => perlin(seed, u * p, v * p, period),
+      Noise::Simplex => simplex_tileable(seed, u, v, period),
+      Noise::Worley => worley(seed, u * p, v * p, period) * 2.0 - 1.0,
+    }
+  }
+}
+
+// 프랙탈 브라운 운동(fBm) 설정
+#[derive(Clone, Copy, Debug)]
+pub struct Fbm {
+  pub octaves: u32,
+  // 옥타브마다 주파수에 곱하는 값
+  pub lacunarity: f32,
+  // 옥타브마다 진폭에 곱하는 값
+  pub gain: f32,
+}
+
+impl Default for Fbm {
+  fn default() -> Self {
+    Self {
+      octaves: 5,
+      lacunarity: 2.0,
+      gain: 0.5,
+    }
+  }
+}
+
+impl Fbm {
+  pub fn sample(&self, noise: Noise, seed: u32, u: f32, v: f32, period: u32) -> f32 {
+    let mut frequency = 1.0;
+    let mut amplitude = 1.0;
+    let mut sum = 0.0;
+    let mut total = 0.0;
+
+    for octave in 0..self.octaves {
+      // 주기를 정수로 반올림해야 옥타브마다 타일링이 유지됩니다.
+      let octave_period = ((period as f32 * frequency).round() as u32).max(1);
+      sum += amplitude * noise.sample(seed.wrapping_add(octave), u, v, octave_period);
+      total += amplitude;
+
+      frequency *= self.lacunarity;
+      amplitude *= self.gain;
+    }
+
+    if total > 0.0 {
+      sum / total
+    } else {
+      0.0
+    }
+  }
+}
+
+impl Image {
+  // 각 픽셀의 텍스처 좌표마다 f를 호출하여 회색조 이미지를 만듭니다.
+  fn from_fn(width: u32, height: u32, f: impl Fn(f32, f32) -> f32) -> Self {
+    let mut image = Image::new(width, height);
+    for j in 0..height {
+      for i in 0..width {
+        let u = i as f32 / width as f32;
+        let v = j as f32 / height as f32;
+        let value = f(u, v).clamp(0.0, 1.0);
+        image.pixels[(i + j * width) as usize] = [value, value, value, 1.0];
+      }
+    }
+    image
+  }
+
+  // period는 이미지 한 장에 들어가는 노이즈 격자의 개수입니다.
+  pub fn noise(width: u32, height: u32, noise: Noise, seed: u32, period: u32) -> Self {
+    Self::from_fn(width, height, |u, v| {
+      noise.sample(seed, u, v, period) * 0.5 + 0.5
+    })
+  }
+
+  pub fn fbm_noise(
+    width: u32, height: u32, noise: Noise, seed: u32, period: u32, fbm: Fbm,
+  ) -> Self {
+    Self::from_fn(width, height, |u, v| {
+      fbm.sample(noise, seed, u, v, period) * 0.5 + 0.5
+    })
+  }
+
+  pub fn checkerboard(
+    width: u32, height: u32, cell_size: u32, color0: [f32; 4], color1: [f32; 4],
+  ) -> Self {
+    let cell_size = cell_size.max(1);
+    let mut image = Image::new(width, height);
+    for j in 0..height {
+      for i in 0..width {
+        let odd = (i / cell_size + j / cell_size) % 2 == 1;
+        image.pixels[(i + j * width) as usize] = if odd { color1 } else { color0 };
+      }
+    }
+    image
+  }
+
+  // 왼쪽(from)에서 오른쪽(to)으로 변하는 선형 그라디언트
+  pub fn linear_gradient(width: u32, height: u32, from: [f32; 4], to: [f32; 4]) -> Self {
+    let mut image = Image::new(width, height);
+    for i in 0..width {
+      let t = if width > 1 {
+        i as f32 / (width - 1) as f32
+      } else {
+        0.0
+      };
+      let color = std::array::from_fn(|c| lerp(from[c], to[c], t));
+      for j in 0..height {
+        image.pixels[(i + j * width) as usize] = color;
+      }
+    }
+    image
+  }
+
+  // 위쪽 2/3은 컬러 바, 아래쪽은 회색조 램프인 테스트 패턴
+  pub fn test_pattern(width: u32, height: u32) -> Self {
+    const BARS: [[f32; 4]; 8] = [
+      [1.0, 1.0, 1.0, 1.0],
+      [1.0, 1.0, 0.0, 1.0],
+      [0.0, 1.0, 1.0, 1.0],
+      [0.0, 1.0, 0.0, 1.0],
+      [1.0, 0.0, 1.0, 1.0],
+      [1.0, 0.0, 0.0, 1.0],
+      [0.0, 0.0, 1.0, 1.0],
+      [0.0, 0.0, 0.0, 1.0],
+    ];
+
+    let mut image = Self::linear_gradient(width, height, [0.0, 0.0, 0.0, 1.0], [1.0; 4]);
+    let bar_height = height * 2 / 3;
+    for j in 0..bar_height {
+      for i in 0..width {
+        let bar = (i * BARS.len() as u32 / width.max(1)) as usize;
+        image.pixels[(i + j * width) as usize] = BARS[bar];
+      }
+    }
+    image
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_rng_is_deterministic() {
+    let mut a = Rng::new(42);
+    let mut b = Rng::new(42);
+    for _ in 0..16 {
+      assert_eq!(a.next_u64(), b.next_u64());
+      let f = a.next_f32();
+      assert_eq!(f, b.next_f32());
+      assert!((0.0..1.0).contains(&f));
+      assert!(a.below(10) < 10);
+      b.below(10);
+    }
+  }
+
+  #[test]
+  fn test_noise_is_tileable() {
+    for noise in [Noise::Perlin, Noise::Simplex, Noise::Worley] {
+      for k in 0..8 {
+        let v = k as f32 / 8.0 + 0.03;
+        let left = noise.sample(7, 0.0, v, 4);
+        let right = noise.sample(7, 1.0, v, 4);
+        assert!((left - right).abs() < 1e-4, "{:?} does not tile", noise);
+
+        let top = noise.sample(7, v, 0.0, 4);
+        let bottom = noise.sample(7, v, 1.0, 4);
+        assert!((top - bottom).abs() < 1e-4, "{:?} does not tile", noise);
+      }
+    }
+  }
+
+  #[test]
+  fn test_noise_is_seeded() {
+    let fbm = Fbm::default();
+    for noise in [Noise::Perlin, Noise::Simplex, Noise::Worley] {
+      let a = Image::fbm_noise(16, 16, noise, 1, 4, fbm);
+      let b = Image::fbm_noise(16, 16, noise, 1, 4, fbm);
+      let c = Image::fbm_noise(16, 16, noise, 2, 4, fbm);
+
+      assert_eq!(a.pixels, b.pixels);
+      assert_ne!(a.pixels, c.pixels);
+      assert!(a.pixels.iter().all(|p| (0.0..=1.0).contains(&p[0])));
+    }
+  }
+
+  #[test]
+  fn test_checkerboard() {
+    let image = Image::checkerboard(4, 4, 2, [0.0; 4], [1.0; 4]);
+    assert_eq!(image.get_pixel(1, 1), &[0.0; 4]);
+    assert_eq!(image.get_pixel(2, 1), &[1.0; 4]);
+    assert_eq!(image.get_pixel(2, 2), &[0.0; 4]);
+  }
+}
@@ -0,0 +1,245 @@
+use super::{BorderMode, Image};
+use glam::Vec3;
+use std::f32::consts::TAU;
+
+// 높이 미분에 사용할 3x3 커널
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DerivativeKernel {
+  Sobel,
+  // 회전 대칭성이 더 좋은 Scharr 커널
+  Scharr,
+}
+
+impl DerivativeKernel {
+  // (가장자리 가중치, 가운데 가중치, 정규화 계수)
+  fn weights(&self) -> (f32, f32, f32) {
+    match self {
+      DerivativeKernel::Sobel => (1.0, 2.0, 1.0 / 8.0),
+      DerivativeKernel::Scharr => (3.0, 10.0, 1.0 / 32.0),
+    }
+  }
+}
+
+// 노멀 맵의 초록 채널 방향
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NormalMapFormat {
+  // +Y가 텍스처의 위쪽 (OpenGL, Blender)
+  OpenGl,
+  // +Y가 텍스처의 아래쪽 (DirectX, Unreal)
+  DirectX,
+}
+
+// 높이 맵의 수평선(horizon) 샘플링으로 AO를 계산하기 위한 설정
+#[derive(Clone, Copy, Debug)]
+pub struct AmbientOcclusion {
+  pub directions: u32,
+  // 픽셀 단위 탐색 거리
+  pub radius: u32,
+  // 높이 1.0이 몇 픽셀에 해당하는지
+  pub height_scale: f32,
+  pub strength: f32,
+}
+
+impl Default for AmbientOcclusion {
+  fn default() -> Self {
+    Self {
+      directions: 8,
+      radius: 8,
+      height_scale: 8.0,
+      strength: 1.0,
+    }
+  }
+}
+
+impl Image {
+  // 높이는 빨강 채널에서 읽습니다.
+  fn height_at(&self, i: i32, j: i32, border: BorderMode) -> f32 {
+    self.get_pixel_border(i, j, border)[0]
+  }
+
+  // 높이 맵에서 탄젠트 공간 노멀 맵을 생성 (RGB = n * 0.5 + 0.5)
+  // 타일링 텍스처는 BorderMode::Wrap을 사용해야 경계에 이음새가 생기지 않습니다.
+  pub fn height_to_normal_map(
+    &self, strength: f32, kernel: DerivativeKernel, format: NormalMapFormat, border: BorderMode,
+  ) -> Image {
+    let (edge, center, norm) = kernel.weights();
+    let mut output = Image::new(self.width, self.height);
+
+    for j in 0..self.height as i32 {
+      for i in 0..self.width as i32 {
+        let h = |di: i32, dj: i32| self.height_at(i + di, j + dj, border);
+
+        let dx = (edge * (h(1, -1) - h(-1, -1))
+          + center * (h(1, 0) - h(-1, 0))
+          + edge * (h(1, 1) - h(-1, 1)))
+          * norm;
+        let dy = (edge * (h(-1, 1) - h(-1, -1))
+          + center * (h(0, 1) - h(0, -1))
+          + edge * (h(1, 1) - h(1, -1)))
+          * norm;
+
+        // 이미지의 j축은 아래 방향이므로 OpenGL 형식에서는 부호가 바뀝니다.
+        let ny = match format {
+          NormalMapFormat::OpenGl => dy,
+          NormalMapFormat::DirectX => -dy,
+        };
+        let n = Vec3::new(-dx * strength, ny * strength, 1.0).normalize();
+
+        *output.get_pixel_mut(i, j) = [n.x * 0.5 + 0.5, n.y * 0.5 + 0.5, n.z * 0.5 + 0.5, 1.0];
+      }
+    }
+
+    output
+  }
+
+  // 여러 방향으로 높이 맵을 따라가며 가장 높은 수평선 각도를 찾고,
+  // 가려진 정도 sin(수평선 각도)의 평균으로 AO를 계산합니다. (1.0 = 가려지지 않음)
+  pub fn height_to_ao_map(&self, params: &AmbientOcclusion, border: BorderMode) -> Image {
+    let directions: Vec<(f32, f32)> = (0..params.directions.max(1))
+      .map(|k| (k as f32 * TAU / params.directions.max(1) as f32).sin_cos())
+      .collect();
+
+    let mut output = Image::new(self.width, self.height);
+    for j in 0..self.height as i32 {
+      for i in 0..self.width as i32 {
+        let h0 = self.height_at(i, j, border) * params.height_scale;
+
+        let mut occlusion = 0.0;
+        for &(sin, cos) in directions.iter() {
+          let mut max_slope = 0.0f32;
+          for step in 1..=params.radius {
+            let x = (i as f32 + cos * step as f32).round() as i32;
+            let y = (j as f32 + sin * step as f32).round() as i32;
+            let h = self.height_at(x, y, border) * params.height_scale;
+            max_slope = max_slope.max((h - h0) / step as f32);
+          }
+
+          // sin(atan(t)) = t / sqrt(1 + t²)
+          occlusion += max_slope / (1.0 + max_slope * max_slope).sqrt();
+        }
+
+        let ao = 1.0 - params.strength * occlusion / directions.len() as f32;
+        let ao = ao.clamp(0.0, 1.0);
+        *output.get_pixel_mut(i, j) = [ao, ao, ao, 1.0];
+      }
+    }
+
+    output
+  }
+
+  // 주변 평균보다 낮은 곳은 어둡게, 높은 곳은 밝게 (0.5 = 평평함)
+  pub fn height_to_cavity_map(&self, radius: u32, strength: f32, border: BorderMode) -> Image {
+    // 가로, 세로 순서의 박스 블러, 가장자리 밖은 border로 읽습니다.
+    let r = radius as i32;
+    let size = (2 * r + 1) as f32;
+    let mut rows = Image::new(self.width, self.height);
+    for j in 0..self.height as i32 {
+      for i in 0..self.width as i32 {
+        let mean = (-r..=r)
+          .map(|d| self.height_at(i + d, j, border))
+          .sum::<f32>()
+          / size;
+        *rows.get_pixel_mut(i, j) = [mean, mean, mean, 1.0];
+      }
+    }
+
+    let mut output = Image::new(self.width, self.height);
+    for j in 0..self.height as i32 {
+      for i in 0..self.width as i32 {
+        let mean = (-r..=r)
+          .map(|d| rows.height_at(i, j + d, border))
+          .sum::<f32>()
+          / size;
+        let cavity = (0.5 + (self.height_at(i, j, border) - mean) * strength).clamp(0.0, 1.0);
+        *output.get_pixel_mut(i, j) = [cavity, cavity, cavity, 1.0];
+      }
+    }
+    output
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn height_map(width: u32, height: u32, f: impl Fn(f32, f32) -> f32) -> Image {
+    let mut image = Image::new(width, height);
+    for j in 0..height {
+      for i in 0..width {
+        let h = f(i as f32, j as f32);
+        image.pixels[(i + j * width) as usize] = [h, h, h, 1.0];
+      }
+    }
+    image
+  }
+
+  #[test]
+  fn test_flat_and_sloped_normals() {
+    let flat = height_map(4, 4, |_, _| 0.5);
+    let normals = flat.height_to_normal_map(
+      1.0,
+      DerivativeKernel::Sobel,
+      NormalMapFormat::OpenGl,
+      BorderMode::Clamp,
+    );
+    assert_eq!(normals.get_pixel(1, 1), &[0.5, 0.5, 1.0, 1.0]);
+
+    // 오른쪽과 아래쪽으로 높아지는 경사
+    let ramp = height_map(8, 8, |x, y| (x + y) * 0.1);
+    for kernel in [DerivativeKernel::Sobel, DerivativeKernel::Scharr] {
+      let gl = ramp.height_to_normal_map(4.0, kernel, NormalMapFormat::OpenGl, BorderMode::Clamp);
+      let dx = ramp.height_to_normal_map(4.0, kernel, NormalMapFormat::DirectX, BorderMode::Clamp);
+
+      let n = gl.get_pixel(4, 4);
+      assert!(n[0] < 0.5 && n[1] > 0.5);
+      assert!((dx.get_pixel(4, 4)[1] - (1.0 - n[1])).abs() < 1e-5);
+    }
+  }
+
+  #[test]
+  fn test_wrap_border_is_seamless() {
+    // 주기가 이미지 너비와 같은 높이 맵
+    let wave = height_map(16, 4, |x, _| (x * TAU / 16.0).sin() * 0.5 + 0.5);
+    let normals = wave.height_to_normal_map(
+      1.0,
+      DerivativeKernel::Scharr,
+      NormalMapFormat::OpenGl,
+      BorderMode::Wrap,
+    );
+
+    // x = 0과 x = 8은 기울기가 반대이고 크기가 같아야 합니다.
+    let left = normals.get_pixel(0, 1)[0] - 0.5;
+    let middle = normals.get_pixel(8, 1)[0] - 0.5;
+    assert!(left < 0.0 && (left + middle).abs() < 1e-5);
+
+    // 골짜기가 경계에 걸쳐 있어도 반대편 가장자리를 이웃으로 봅니다.
+    let cavity = wave.height_to_cavity_map(2, 4.0, BorderMode::Wrap);
+    for (a, b) in [(0, 8), (4, 12), (12, 4)] {
+      let (ca, cb) = (
+        cavity.get_pixel(a, 1)[0] - 0.5,
+        cavity.get_pixel(b, 1)[0] - 0.5,
+      );
+      assert!((ca + cb).abs() < 1e-5, "{a} {b}");
+    }
+    assert!(cavity.get_pixel(12, 1)[0] < 0.5);
+  }
+
+  #[test]
+  fn test_ao_and_cavity() {
+    let flat = height_map(8, 8, |_, _| 0.5);
+    let ao = flat.height_to_ao_map(&AmbientOcclusion::default(), BorderMode::Clamp);
+    assert!(ao.pixels.iter().all(|p| p[0] == 1.0));
+
+    // 가운데가 움푹 파인 높이 맵
+    let pit = height_map(16, 16, |x, y| {
+      ((x - 8.0).powi(2) + (y - 8.0).powi(2)).sqrt().min(6.0) / 6.0
+    });
+    let ao = pit.height_to_ao_map(&AmbientOcclusion::default(), BorderMode::Clamp);
+    assert!(ao.get_pixel(8, 8)[0] < 0.5);
+    assert!(ao.get_pixel(8, 8)[0] < ao.get_pixel(0, 0)[0]);
+
+    let cavity = pit.height_to_cavity_map(2, 4.0, BorderMode::Clamp);
+    assert!(cavity.get_pixel(8, 8)[0] < 0.5);
+    assert!((cavity.get_pixel(0, 0)[0] - 0.5).abs() < 1e-5);
+  }
+}
@@ -0,0 +1,302 @@
+use super::Image;
+use std::collections::VecDeque;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Connectivity {
+  Four,
+  Eight,
+}
+
+impl Connectivity {
+  fn offsets(&self) -> &'static [(i32, i32)] {
+    match self {
+      Connectivity::Four => &[(1, 0), (-1, 0), (0, 1), (0, -1)],
+      Connectivity::Eight => &[
+        (1, 0),
+        (-1, 0),
+        (0, 1),
+        (0, -1),
+        (1, 1),
+        (1, -1),
+        (-1, 1),
+        (-1, -1),
+      ],
+    }
+  }
+}
+
+// 연결 요소 레이블링 결과, 0은 배경이고 1..=count가 각 영역입니다.
+pub struct Labels {
+  pub width: u32,
+  pub height: u32,
+  pub labels: Vec<u32>,
+  pub count: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegionStats {
+  pub label: u32,
+  pub area: u32,
+  // (x_min, y_min, x_max, y_max), 양 끝 포함
+  pub bounding_box: (u32, u32, u32, u32),
+  pub centroid: (f32, f32),
+  pub mean_color: [f32; 4],
+}
+
+// 네 채널 중 가장 큰 차이가 tolerance 이하이면 같은 색으로 봅니다.
+fn within_tolerance(a: &[f32; 4], b: &[f32; 4], tolerance: f32) -> bool {
+  a.iter().zip(b).all(|(x, y)| (x - y).abs() <= tolerance)
+}
+
+// mask가 true인 픽셀들을 연결 요소로 묶습니다.
+pub fn label_components(
+  mask: &[bool], width: u32, height: u32, connectivity: Connectivity,
+) -> Labels {
+  assert_eq!(mask.len(), (width * height) as usize, "Mask size mismatch");
+
+  let mut labels = vec![0; mask.len()];
+  let mut count = 0;
+  let mut queue = VecDeque::new();
+
+  for start in 0..mask.len() {
+    if !mask[start] || labels[start] != 0 {
+      continue;
+    }
+
+    count += 1;
+    labels[start] = count;
+    queue.push_back(start);
+
+    while let Some(index) = queue.pop_front() {
+      let (x, y) = ((index as u32 % width) as i32, (index as u32 / width) as i32);
+      for (dx, dy) in connectivity.offsets() {
+        let (nx, ny) = (x + dx, y + dy);
+        if nx < 0 || ny < 0 || nx >= width as i32 || ny >= height as i32 {
+          continue;
+        }
+
+        let neighbor = (nx + ny * width as i32) as usize;
+        if mask[neighbor] && labels[neighbor] == 0 {
+          labels[neighbor] = count;
+          queue.push_back(neighbor);
+        }
+      }
+    }
+  }
+
+  Labels {
+    width,
+    height,
+    labels,
+    count,
+  }
+}
+
+impl Labels {
+  // 각 레이블마다 구분되는 색을 칠한 이미지 (배경은 검정)
+  pub fn to_image(&self) -> Image {
+    let mut image = Image::new(self.width, self.height);
+    for (pixel, &label) in image.pixels.iter_mut().zip(&self.labels) {
+      if label != 0 {
+        // 황금각만큼 색상(hue)을 돌려서 이웃한 레이블끼리 색이 겹치지 않게 합니다.
+        let hue = (label as f32 * 0.618_034).fract() * 6.0;
+        let x = 1.0 - (hue % 2.0 - 1.0).abs();
+        let (r, g, b) = match hue as u32 {
+          0 => (1.0, x, 0.0),
+          1 => (x, 1.0, 0.0),
+          2 => (0.0, 1.0, x),
+          3 => (0.0, x, 1.0),
+          4 => (x, 0.0, 1.0),
+          _ => (1.0, 0.0, x),
+        };
+        *pixel = [r, g, b, 1.0];
+      } else {
+        *pixel = [0.0, 0.0, 0.0, 1.0];
+      }
+    }
+    image
+  }
+
+  // 각 영역의 넓이, 경계 상자, 무게중심, image에서의 평균 색
+  pub fn region_stats(&self, image: &Image) -> Vec<RegionStats> {
+    assert!(
+      image.width == self.width && image.height == self.height,
+      "Image size mismatch"
+    );
+
+    let mut stats: Vec<RegionStats> = (1..=self.count)
+      .map(|label| RegionStats {
+        label,
+        area: 0,
+        bounding_box: (u32::MAX, u32::MAX, 0, 0),
+        centroid: (0.0, 0.0),
+        mean_color: [0.0; 4],
+      })
+      .collect();
+
+    for (index, &label) in self.labels.iter().enumerate() {
+      if label == 0 {
+        continue;
+      }
+
+      let (x, y) = (index as u32 % self.width, index as u32 / self.width);
+      let region = &mut stats[label as usize - 1];
+      region.area += 1;
+
+      let (x_min, y_min, x_max, y_max) = region.bounding_box;
+      region.bounding_box = (x_min.min(x), y_min.min(y), x_max.max(x), y_max.max(y));
+      region.centroid.0 += x as f32;
+      region.centroid.1 += y as f32;
+      for (sum, v) in region.mean_color.iter_mut().zip(image.pixels[index]) {
+        *sum += v;
+      }
+    }
+
+    for region in stats.iter_mut() {
+      let area = region.area as f32;
+      region.centroid = (region.centroid.0 / area, region.centroid.1 / area);
+      region.mean_color = region.mean_color.map(|v| v / area);
+    }
+    stats
+  }
+}
+
+impl Image {
+  // 스캔라인 플러드 필: 시작 픽셀과 색 차이가 tolerance 이하인 연결된 픽셀을 color로 칠합니다.
+  // 칠한 픽셀 수를 반환합니다.
+  pub fn flood_fill(&mut self, i: i32, j: i32, color: [f32; 4], tolerance: f32) -> usize {
+    let (width, height) = (self.width as i32, self.height as i32);
+    if i < 0 || j < 0 || i >= width || j >= height {
+      return 0;
+    }
+
+    let seed = *self.get_pixel(i, j);
+    // 새 색이 tolerance 안에 있어도 무한 반복하지 않도록 방문 여부를 따로 기록
+    let mut visited = vec![false; self.pixels.len()];
+    let matches = |image: &Image, visited: &[bool], x: i32, y: i32| {
+      !visited[(x + y * width) as usize]
+        && within_tolerance(image.get_pixel(x, y), &seed, tolerance)
+    };
+
+    let mut filled = 0;
+    let mut stack = vec![(i, j)];
+    while let Some((x, y)) = stack.pop() {
+      if !matches(self, &visited, x, y) {
+        continue;
+      }
+
+      // 현재 행에서 좌우로 최대한 확장
+      let mut left = x;
+      while left > 0 && matches(self, &visited, left - 1, y) {
+        left -= 1;
+      }
+      let mut right = x;
+      while right < width - 1 && matches(self, &visited, right + 1, y) {
+        right += 1;
+      }
+
+      for sx in left..=right {
+        visited[(sx + y * width) as usize] = true;
+        *self.get_pixel_mut(sx, y) = color;
+        filled += 1;
+      }
+
+      // 위, 아래 행에서 새로 시작할 구간의 첫 픽셀만 스택에 추가
+      for ny in [y - 1, y + 1] {
+        if ny < 0 || ny >= height {
+          continue;
+        }
+
+        let mut in_span = false;
+        for sx in left..=right {
+          let m = matches(self, &visited, sx, ny);
+          if m && !in_span {
+            stack.push((sx, ny));
+          }
+          in_span = m;
+        }
+      }
+    }
+
+    filled
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::image::sdf::MaskChannel;
+
+  const WHITE: [f32; 4] = [1.0; 4];
+
+  #[test]
+  fn test_flood_fill_stops_at_border() {
+    let mut image = Image::new(10, 10);
+    image.draw_rect(2, 2, 6, 6, WHITE);
+
+    // 사각형 안쪽 4x4만 채워집니다.
+    let filled = image.flood_fill(4, 4, [1.0, 0.0, 0.0, 1.0], 0.1);
+    assert_eq!(filled, 16);
+    assert_eq!(image.get_pixel(3, 3), &[1.0, 0.0, 0.0, 1.0]);
+    assert_eq!(image.get_pixel(0, 0), &[0.0; 4]);
+
+    // 시작 색과 비슷한 색으로 채워도 끝나야 합니다.
+    let filled = image.flood_fill(0, 0, [0.05, 0.0, 0.0, 0.0], 0.1);
+    assert_eq!(filled, 100 - 16 - 20);
+  }
+
+  #[test]
+  fn test_flood_fill_tolerance() {
+    let mut image = Image::linear_gradient(11, 1, [0.0; 4], [1.0; 4]);
+    let filled = image.flood_fill(0, 0, [0.0, 1.0, 0.0, 1.0], 0.25);
+    assert_eq!(filled, 3);
+  }
+
+  #[test]
+  fn test_labels_and_stats() {
+    let mut image = Image::new(12, 8);
+    image.fill_rect(1, 1, 3, 2, WHITE);
+    image.fill_rect(6, 2, 4, 4, [0.5, 0.5, 0.5, 1.0]);
+    // 대각선으로만 이어진 픽셀
+    image.fill_rect(10, 6, 1, 1, WHITE);
+
+    let mask = image.mask(MaskChannel::Alpha, 0.5);
+    let four = label_components(&mask, 12, 8, Connectivity::Four);
+    let eight = label_components(&mask, 12, 8, Connectivity::Eight);
+    assert_eq!(four.count, 3);
+    assert_eq!(eight.count, 2);
+
+    let stats = four.region_stats(&image);
+    assert_eq!(stats[0].area, 6);
+    assert_eq!(stats[0].bounding_box, (1, 1, 3, 2));
+    assert_eq!(stats[0].centroid, (2.0, 1.5));
+    assert_eq!(stats[1].mean_color, [0.5, 0.5, 0.5, 1.0]);
+  }
+
+  #[test]
+  fn test_background_regions_detect_cracks() {
+    // 배경이 하나로 이어져 있으면 틈이 없는 것입니다.
+    let mut image = Image::new(8, 8);
+    image.fill_rect(2, 2, 4, 4, WHITE);
+    let background: Vec<bool> = image
+      .mask(MaskChannel::Alpha, 0.5)
+      .iter()
+      .map(|m| !m)
+      .collect();
+    assert_eq!(
+      label_components(&background, 8, 8, Connectivity::Four).count,
+      1
+    );
+
+    // 가운데 구멍이 생기면 배경이 두 영역으로 나뉩니다.
+    *image.get_pixel_mut(3, 3) = [0.0; 4];
+    let background: Vec<bool> = image
+      .mask(MaskChannel::Alpha, 0.5)
+      .iter()
+      .map(|m| !m)
+      .collect();
+    let labels = label_components(&background, 8, 8, Connectivity::Four);
+    assert_eq!(labels.count, 2);
+    assert_eq!(labels.to_image().pixels.len(), 64);
+  }
+}
@@ -0,0 +1,189 @@
+use super::{luminance, Image};
+
+// 마스크를 만들 때 사용할 채널
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MaskChannel {
+  Alpha,
+  Luminance,
+}
+
+// 특징 픽셀이 없는 위치의 초기값 (무한대를 쓰면 뺄셈에서 NaN이 생깁니다)
+const FAR: f32 = 1e20;
+
+// Felzenszwalb & Huttenlocher의 1D 제곱 거리 변환
+// 포물선들의 하한 포락선(lower envelope)을 O(n)에 구합니다.
+fn distance_transform_1d(f: &[f32], d: &mut [f32], v: &mut [usize], z: &mut [f32]) {
+  let n = f.len();
+  if n == 0 {
+    return;
+  }
+
+  let mut k = 0;
+  v[0] = 0;
+  z[0] = f32::NEG_INFINITY;
+  z[1] = f32::INFINITY;
+
+  for q in 1..n {
+    let intersect =
+      |p: usize| ((f[q] + (q * q) as f32) - (f[p] + (p * p) as f32)) / (2 * q - 2 * p) as f32;
+
+    let mut s = intersect(v[k]);
+    while s <= z[k] {
+      k -= 1;
+      s = intersect(v[k]);
+    }
+
+    k += 1;
+    v[k] = q;
+    z[k] = s;
+    z[k + 1] = f32::INFINITY;
+  }
+
+  k = 0;
+  for (q, out) in d.iter_mut().enumerate() {
+    while z[k + 1] < q as f32 {
+      k += 1;
+    }
+    let dq = q as f32 - v[k] as f32;
+    *out = dq * dq + f[v[k]];
+  }
+}
+
+// mask가 true인 가장 가까운 픽셀까지의 정확한 유클리드 거리
+pub fn distance_transform(mask: &[bool], width: u32, height: u32) -> Vec<f32> {
+  let (w, h) = (width as usize, height as usize);
+  assert_eq!(mask.len(), w * h, "Mask size mismatch");
+
+  let mut grid: Vec<f32> = mask.iter().map(|&m| if m { 0.0 } else { FAR }).collect();
+
+  let n = w.max(h);
+  let (mut f, mut d) = (vec![0.0; n], vec![0.0; n]);
+  let (mut v, mut z) = (vec![0; n], vec![0.0; n + 1]);
+
+  // 열 방향
+  for x in 0..w {
+    for y in 0..h {
+      f[y] = grid[x + y * w];
+    }
+    distance_transform_1d(&f[..h], &mut d[..h], &mut v, &mut z);
+    for y in 0..h {
+      grid[x + y * w] = d[y];
+    }
+  }
+
+  // 행 방향
+  for row in grid.chunks_mut(w.max(1)) {
+    f[..w].copy_from_slice(row);
+    distance_transform_1d(&f[..w], &mut d[..w], &mut v, &mut z);
+    row.copy_from_slice(&d[..w]);
+  }
+
+  grid.iter().map(|d| d.sqrt()).collect()
+}
+
+impl Image {
+  pub fn mask(&self, channel: MaskChannel, threshold: f32) -> Vec<bool> {
+    self
+      .pixels
+      .iter()
+      .map(|p| match channel {
+        MaskChannel::Alpha => p[3] >= threshold,
+        MaskChannel::Luminance => luminance(p) >= threshold,
+      })
+      .collect()
+  }
+
+  // 경계에서 픽셀 단위의 부호 있는 거리 (안쪽이 음수)
+  // 경계는 안쪽과 바깥쪽 픽셀 중심의 가운데에 있다고 보고 0.5만큼 보정합니다.
+  pub fn signed_distances(&self, channel: MaskChannel, threshold: f32) -> Vec<f32> {
+    let mask = self.mask(channel, threshold);
+    let inverted: Vec<bool> = mask.iter().map(|m| !m).collect();
+
+    let to_inside = distance_transform(&mask, self.width, self.height);
+    let to_outside = distance_transform(&inverted, self.width, self.height);
+
+    mask
+      .iter()
+      .enumerate()
+      .map(|(k, &inside)| {
+        if inside {
+          -(to_outside[k] - 0.5)
+        } else {
+          to_inside[k] - 0.5
+        }
+      })
+      .collect()
+  }
+
+  // spread 픽셀 거리를 [0, 1]로 정규화한 SDF 텍스처 (0.5가 경계, 안쪽이 더 밝음)
+  pub fn signed_distance_field(&self, channel: MaskChannel, threshold: f32, spread: f32) -> Image {
+    let mut output = Image::new(self.width, self.height);
+    for (pixel, d) in output
+      .pixels
+      .iter_mut()
+      .zip(self.signed_distances(channel, threshold))
+    {
+      let value = (0.5 - d / (2.0 * spread)).clamp(0.0, 1.0);
+      *pixel = [value, value, value, 1.0];
+    }
+    output
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::image::noise::Rng;
+
+  #[test]
+  fn test_matches_brute_force() {
+    let (width, height) = (13, 9);
+    let mut rng = Rng::new(3);
+    let mask: Vec<bool> = (0..width * height).map(|_| rng.below(10) == 0).collect();
+    let distances = distance_transform(&mask, width, height);
+
+    for y in 0..height as i32 {
+      for x in 0..width as i32 {
+        let mut best = FAR.sqrt();
+        for (k, _) in mask.iter().enumerate().filter(|(_, &m)| m) {
+          let (px, py) = ((k as u32 % width) as i32, (k as u32 / width) as i32);
+          best = best.min((((px - x).pow(2) + (py - y).pow(2)) as f32).sqrt());
+        }
+        let got = distances[(x + y * width as i32) as usize];
+        assert!(
+          (got - best).abs() < 1e-3,
+          "({}, {}): {} vs {}",
+          x,
+          y,
+          got,
+          best
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn test_mask_channels() {
+    // 투명한 흰색과 불투명한 검정
+    let image = Image::from_pixels(2, 1, vec![[1.0, 1.0, 1.0, 0.0], [0.0, 0.0, 0.0, 1.0]]);
+    assert_eq!(image.mask(MaskChannel::Alpha, 0.5), vec![false, true]);
+    assert_eq!(image.mask(MaskChannel::Luminance, 0.5), vec![true, false]);
+  }
+
+  #[test]
+  fn test_signed_distance_field_of_disc() {
+    let mut image = Image::new(32, 32);
+    image.fill_circle(16, 16, 8, [1.0; 4]);
+    let sdf = image.signed_distance_field(MaskChannel::Alpha, 0.5, 4.0);
+
+    assert_eq!(sdf.get_pixel(16, 16)[0], 1.0);
+    assert_eq!(sdf.get_pixel(0, 0)[0], 0.0);
+    // 경계 바로 안쪽과 바깥쪽
+    assert!(sdf.get_pixel(16, 8)[0] > 0.5);
+    assert!(sdf.get_pixel(16, 7)[0] < 0.5);
+
+    // 반지름 8인 원의 중심은 경계에서 약 8픽셀 안쪽
+    let distances = image.signed_distances(MaskChannel::Alpha, 0.5);
+    assert!((distances[16 + 16 * 32] + 8.0).abs() < 0.5);
+  }
+}
@@ -0,0 +1,324 @@
+use super::{luminance, Image};
+
+// 보호 마스크에 더하고 제거 마스크에서 빼는 에너지, 어떤 경로 합보다도 커야 합니다.
+const MASK_ENERGY: f32 = 1e6;
+
+// 심 카빙에 사용할 마스크, 비어 있으면 사용하지 않습니다. (Image::mask로 만들 수 있습니다)
+#[derive(Clone, Debug, Default)]
+pub struct SeamMasks {
+  // 심이 지나가지 않아야 할 픽셀
+  pub protect: Vec<bool>,
+  // 먼저 지워야 할 물체의 픽셀
+  pub remove: Vec<bool>,
+}
+
+// 세로 심만 다루고, 가로 심은 전치해서 같은 코드로 처리합니다.
+#[derive(Clone)]
+struct Carver {
+  width: usize,
+  height: usize,
+  pixels: Vec<[f32; 4]>,
+  protect: Vec<bool>,
+  remove: Vec<bool>,
+  // 각 픽셀의 원래 열 번호 (심을 삽입할 위치를 찾을 때 사용)
+  columns: Vec<u32>,
+}
+
+fn transposed<T: Copy>(data: &[T], width: usize, height: usize) -> Vec<T> {
+  (0..width * height)
+    .map(|k| data[k / height + (k % height) * width])
+    .collect()
+}
+
+// 각 행에서 seam[j]번째 원소를 지웁니다.
+fn remove_seam_from<T>(data: &mut Vec<T>, width: usize, seam: &[usize]) {
+  let mut k = 0;
+  data.retain(|_| {
+    k += 1;
+    seam[(k - 1) / width] != (k - 1) % width
+  });
+}
+
+impl Carver {
+  fn new(image: &Image, masks: &SeamMasks) -> Self {
+    let n = image.pixels.len();
+    let mask = |m: &Vec<bool>| {
+      assert!(m.is_empty() || m.len() == n, "Mask size mismatch");
+      if m.is_empty() {
+        vec![false; n]
+      } else {
+        m.clone()
+      }
+    };
+
+    Self {
+      width: image.width as usize,
+      height: image.height as usize,
+      pixels: image.pixels.clone(),
+      protect: mask(&masks.protect),
+      remove: mask(&masks.remove),
+      columns: (0..n).map(|k| (k % image.width as usize) as u32).collect(),
+    }
+  }
+
+  fn transpose(&mut self) {
+    let (w, h) = (self.width, self.height);
+    self.pixels = transposed(&self.pixels, w, h);
+    self.protect = transposed(&self.protect, w, h);
+    self.remove = transposed(&self.remove, w, h);
+    self.columns = (0..w * h).map(|k| (k % h) as u32).collect();
+    (self.width, self.height) = (h, w);
+  }
+
+  // 밝기 기울기의 L1 크기에 마스크 에너지를 더한 값
+  fn energy(&self) -> Vec<f32> {
+    let (w, h) = (self.width as i32, self.height as i32);
+    let luma: Vec<f32> = self.pixels.iter().map(luminance).collect();
+    let l = |i: i32, j: i32| luma[(i.clamp(0, w - 1) + j.clamp(0, h - 1) * w) as usize];
+
+    (0..w * h)
+      .map(|k| {
+        let (i, j) = (k % w, k / w);
+        let mut e = (l(i + 1, j) - l(i - 1, j)).abs() + (l(i, j + 1) - l(i, j - 1)).abs();
+        if self.protect[k as usize] {
+          e += MASK_ENERGY;
+        }
+        if self.remove[k as usize] {
+          e -= MASK_ENERGY;
+        }
+        e
+      })
+      .collect()
+  }
+
+  // 동적 계획법으로 위에서 아래로 에너지 합이 가장 작은 8-연결 경로를 찾습니다.
+  fn find_seam(&self) -> Vec<usize> {
+    let (w, h) = (self.width, self.height);
+    let mut cost = self.energy();
+    for j in 1..h {
+      for i in 0..w {
+        let above = &cost[(j - 1) * w..j * w];
+        let best = above[i.saturating_sub(1)..(i + 2).min(w)]
+          .iter()
+          .fold(f32::INFINITY, |a, &b| a.min(b));
+        cost[i + j * w] += best;
+      }
+    }
+
+    let last = &cost[(h - 1) * w..];
+    let mut i = (0..w).min_by(|&a, &b| last[a].total_cmp(&last[b])).unwrap();
+    let mut seam = vec![0; h];
+    seam[h - 1] = i;
+    for j in (0..h - 1).rev() {
+      let row = &cost[j * w..(j + 1) * w];
+      i = (i.saturating_sub(1)..(i + 2).min(w))
+        .min_by(|&a, &b| row[a].total_cmp(&row[b]))
+        .unwrap();
+      seam[j] = i;
+    }
+    seam
+  }
+
+  fn remove_seam(&mut self, seam: &[usize]) {
+    let w = self.width;
+    remove_seam_from(&mut self.pixels, w, seam);
+    remove_seam_from(&mut self.protect, w, seam);
+    remove_seam_from(&mut self.remove, w, seam);
+    remove_seam_from(&mut self.columns, w, seam);
+    self.width -= 1;
+  }
+
+  // 복사본에서 count개의 심을 지운 뒤, 그 심들을 원본에서 이웃과의 평균으로 복제합니다.
+  // 한 번에 너무 많이 넣으면 같은 심이 반복되므로 너비의 절반씩 나눠서 넣습니다.
+  fn insert_seams(&mut self, mut count: usize) {
+    while count > 0 {
+      let batch = count.min((self.width / 2).max(1));
+      let mut copy = self.clone();
+      copy.columns = (0..self.width * self.height)
+        .map(|k| (k % self.width) as u32)
+        .collect();
+
+      let mut duplicates = vec![0u32; self.width * self.height];
+      for _ in 0..batch {
+        let seam = copy.find_seam();
+        for (j, &i) in seam.iter().enumerate() {
+          duplicates[copy.columns[i + j * copy.width] as usize + j * self.width] += 1;
+        }
+        if copy.width > 1 {
+          copy.remove_seam(&seam);
+        }
+      }
+
+      let (w, h) = (self.width, self.height);
+      let new_width = w + batch;
+      let mut pixels = Vec::with_capacity(new_width * h);
+      let mut protect = Vec::with_capacity(new_width * h);
+      for j in 0..h {
+        for i in 0..w {
+          let k = i + j * w;
+          let p = self.pixels[k];
+          let right = self.pixels[(i + 1).min(w - 1) + j * w];
+          pixels.push(p);
+          protect.push(self.protect[k]);
+          for _ in 0..duplicates[k] {
+            pixels.push(std::array::from_fn(|c| (p[c] + right[c]) * 0.5));
+            protect.push(self.protect[k]);
+          }
+        }
+      }
+
+      self.pixels = pixels;
+      self.protect = protect;
+      self.remove = vec![false; new_width * h];
+      self.columns = (0..new_width * h).map(|k| (k % new_width) as u32).collect();
+      self.width = new_width;
+      count -= batch;
+    }
+  }
+
+  fn resize_width(&mut self, width: usize) {
+    while self.width > width {
+      let seam = self.find_seam();
+      self.remove_seam(&seam);
+    }
+    if self.width < width {
+      self.insert_seams(width - self.width);
+    }
+  }
+
+  fn into_image(self) -> Image {
+    Image::from_pixels(self.width as u32, self.height as u32, self.pixels)
+  }
+}
+
+impl Image {
+  // 심 카빙에 사용하는 에너지 맵 (밝기 기울기의 L1 크기)
+  pub fn seam_energy(&self) -> Image {
+    let carver = Carver::new(self, &SeamMasks::default());
+    let pixels = carver
+      .energy()
+      .into_iter()
+      .map(|e| [e, e, e, 1.0])
+      .collect();
+    Image::from_pixels(self.width, self.height, pixels)
+  }
+
+  // 내용을 고려한 크기 변경: 에너지가 낮은 세로/가로 심을 지우거나 복제합니다.
+  // 제거 마스크가 있으면 먼저 그 물체가 사라질 때까지 심을 지운 뒤 목표 크기로 맞춥니다.
+  pub fn seam_carve(&self, width: u32, height: u32, masks: &SeamMasks) -> Image {
+    assert!(width > 0 && height > 0, "Target size must be positive");
+    let mut carver = Carver::new(self, masks);
+
+    // 물체의 폭과 높이 중 짧은 쪽 방향으로 지워야 심을 덜 지웁니다.
+    let (mut min_i, mut max_i, mut min_j, mut max_j) = (usize::MAX, 0, usize::MAX, 0);
+    for (k, _) in carver.remove.iter().enumerate().filter(|(_, &r)| r) {
+      let (i, j) = (k % carver.width, k / carver.width);
+      (min_i, max_i) = (min_i.min(i), max_i.max(i));
+      (min_j, max_j) = (min_j.min(j), max_j.max(j));
+    }
+    if min_i <= max_i {
+      let transpose = max_i - min_i > max_j - min_j;
+      if transpose {
+        carver.transpose();
+      }
+      while carver.width > 1 && carver.remove.iter().any(|&r| r) {
+        let seam = carver.find_seam();
+        carver.remove_seam(&seam);
+      }
+      carver.remove.fill(false);
+      if transpose {
+        carver.transpose();
+      }
+    }
+
+    carver.resize_width(width as usize);
+    carver.transpose();
+    carver.resize_width(height as usize);
+    carver.transpose();
+    carver.into_image()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::image::noise::Rng;
+
+  const RED: [f32; 4] = [1.0, 0.0, 0.0, 1.0];
+
+  fn noisy(width: u32, height: u32, seed: u64) -> Image {
+    let mut rng = Rng::new(seed);
+    let mut image = Image::new(width, height);
+    for pixel in image.pixels.iter_mut() {
+      let v = rng.next_f32();
+      *pixel = [v, v, v, 1.0];
+    }
+    image
+  }
+
+  fn count(image: &Image, color: [f32; 4]) -> usize {
+    image.pixels.iter().filter(|&&p| p == color).count()
+  }
+
+  #[test]
+  fn test_seams_avoid_content() {
+    let mut image = Image::new(40, 24);
+    image.as_view_mut().fill([0.5, 0.5, 0.5, 1.0]);
+    image.fill_rect(17, 9, 6, 6, RED);
+
+    // 평평한 곳은 에너지가 없고 사각형의 가장자리에만 에너지가 있습니다.
+    let energy = image.seam_energy();
+    assert_eq!(energy.get_pixel(2, 2)[0], 0.0);
+    assert!(energy.get_pixel(17, 12)[0] > 0.0);
+
+    let carved = image.seam_carve(30, 18, &SeamMasks::default());
+    assert_eq!((carved.width, carved.height), (30, 18));
+    assert_eq!(count(&carved, RED), 36);
+  }
+
+  #[test]
+  fn test_protect_mask() {
+    let image = noisy(40, 20, 1);
+    let mut protect = vec![false; 40 * 20];
+    for (k, p) in protect.iter_mut().enumerate() {
+      *p = k % 40 < 10;
+    }
+    let masks = SeamMasks {
+      protect,
+      ..Default::default()
+    };
+
+    let carved = image.seam_carve(30, 20, &masks);
+    for j in 0..20 {
+      for i in 0..10 {
+        assert_eq!(carved.get_pixel(i, j), image.get_pixel(i, j));
+      }
+    }
+  }
+
+  #[test]
+  fn test_remove_mask_keeps_size() {
+    let mut image = noisy(32, 24, 2);
+    image.fill_rect(12, 6, 4, 10, RED);
+    let masks = SeamMasks {
+      remove: image.pixels.iter().map(|&p| p == RED).collect(),
+      ..Default::default()
+    };
+
+    let carved = image.seam_carve(32, 24, &masks);
+    assert_eq!((carved.width, carved.height), (32, 24));
+    assert_eq!(count(&carved, RED), 0);
+  }
+
+  #[test]
+  fn test_insert_seams_keeps_gradient() {
+    let image = Image::linear_gradient(20, 8, [0.0, 0.0, 0.0, 1.0], [1.0; 4]);
+    let wide = image.seam_carve(34, 8, &SeamMasks::default());
+    assert_eq!(wide.width, 34);
+    for j in 0..8 {
+      for i in 1..34 {
+        assert!(wide.get_pixel(i, j)[0] >= wide.get_pixel(i - 1, j)[0]);
+      }
+    }
+  }
+}
@@ -0,0 +1,116 @@
+use super::{luminance, Image};
+
+impl Image {
+  // 언샤프 마스크: 원본 + amount * (원본 - 가우시안 블러)
+  // radius는 블러의 표준편차(픽셀)이고, 밝기 차이가 threshold보다 작은 픽셀은
+  // 노이즈로 보고 선명하게 하지 않습니다. 알파 채널은 그대로 둡니다.
+  pub fn unsharp_mask(&mut self, amount: f32, radius: f32, threshold: f32) {
+    let mut blurred = self.clone();
+    blurred.gaussian_blur_sigma(radius);
+
+    for (pixel, low) in self.pixels.iter_mut().zip(&blurred.pixels) {
+      if (luminance(pixel) - luminance(low)).abs() < threshold {
+        continue;
+      }
+      for c in 0..3 {
+        pixel[c] += amount * (pixel[c] - low[c]);
+      }
+    }
+  }
+
+  // 하이패스: 0.5 + (원본 - 가우시안 블러), 평평한 영역은 회색(0.5)이 됩니다.
+  pub fn high_pass(&self, radius: f32) -> Image {
+    let mut output = self.clone();
+    output.gaussian_blur_sigma(radius);
+
+    for (low, pixel) in output.pixels.iter_mut().zip(&self.pixels) {
+      for c in 0..3 {
+        low[c] = 0.5 + pixel[c] - low[c];
+      }
+      low[3] = pixel[3];
+    }
+    output
+  }
+
+  // 클래리티(clarity) 방식의 국소 대비 강화
+  // 큰 반경의 블러로 구한 주변 밝기와의 차이를 키우되, 중간 톤에 가장 강하게 적용해서
+  // 하이라이트와 그림자가 잘리지 않게 합니다. 색상 비율을 유지하도록 RGB에 같은 배율을 곱합니다.
+  pub fn clarity(&mut self, amount: f32, radius: f32) {
+    let mut luma = Image::new(self.width, self.height);
+    for (l, pixel) in luma.pixels.iter_mut().zip(&self.pixels) {
+      l[0] = luminance(pixel);
+    }
+    luma.gaussian_blur_sigma(radius);
+
+    for (pixel, local) in self.pixels.iter_mut().zip(&luma.pixels) {
+      let l = luminance(pixel);
+      let midtone = (1.0 - (2.0 * l - 1.0).powi(2)).max(0.0);
+      let target = (l + amount * midtone * (l - local[0])).max(0.0);
+
+      if l > 1e-6 {
+        let scale = target / l;
+        for v in pixel.iter_mut().take(3) {
+          *v *= scale;
+        }
+      } else {
+        for v in pixel.iter_mut().take(3) {
+          *v += target - l;
+        }
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // 왼쪽 절반은 어둡고 오른쪽 절반은 밝은 이미지
+  fn step_edge() -> Image {
+    let mut image = Image::new(16, 4);
+    image.fill_rect(0, 0, 8, 4, [0.25, 0.25, 0.25, 1.0]);
+    image.fill_rect(8, 0, 8, 4, [0.75, 0.75, 0.75, 1.0]);
+    image
+  }
+
+  #[test]
+  fn test_unsharp_mask_overshoots_edges() {
+    let mut image = step_edge();
+    image.unsharp_mask(1.0, 1.0, 0.0);
+
+    // 경계의 양쪽에서 대비가 커지고, 경계에서 먼 곳은 그대로입니다.
+    assert!(image.get_pixel(7, 1)[0] < 0.25);
+    assert!(image.get_pixel(8, 1)[0] > 0.75);
+    assert!((image.get_pixel(0, 1)[0] - 0.25).abs() < 1e-5);
+    assert_eq!(image.get_pixel(8, 1)[3], 1.0);
+
+    // 차이가 threshold보다 작으면 변하지 않습니다.
+    let mut image = step_edge();
+    image.unsharp_mask(1.0, 1.0, 0.5);
+    assert_eq!(image.pixels, step_edge().pixels);
+  }
+
+  #[test]
+  fn test_high_pass_and_clarity() {
+    let mut flat = Image::new(8, 8);
+    flat.as_view_mut().fill([0.3, 0.6, 0.9, 1.0]);
+    let high = flat.high_pass(2.0);
+    assert!(high.pixels.iter().all(|p| (p[0] - 0.5).abs() < 1e-5));
+
+    let original = step_edge();
+    let high = original.high_pass(1.0);
+    assert!(high.get_pixel(7, 0)[0] < 0.5 && high.get_pixel(8, 0)[0] > 0.5);
+
+    // 평평한 이미지는 그대로, 경계 근처의 대비는 커집니다.
+    let mut image = flat.clone();
+    image.clarity(1.0, 4.0);
+    for (a, b) in image.pixels.iter().zip(&flat.pixels) {
+      assert!(a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-5));
+    }
+
+    let mut image = step_edge();
+    image.clarity(1.0, 4.0);
+    let contrast = |img: &Image| img.get_pixel(9, 0)[0] - img.get_pixel(6, 0)[0];
+    assert!(contrast(&image) > contrast(&original));
+  }
+}
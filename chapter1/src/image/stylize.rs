@@ -0,0 +1,346 @@
+use super::{luminance, Image};
+use std::f32::consts::{PI, TAU};
+
+// 쿠와하라 필터의 부채꼴 개수
+const KUWAHARA_SECTORS: usize = 8;
+
+// CMYK 스크린 각도 (도)
+const SCREEN_ANGLES: [f32; 4] = [15.0, 75.0, 0.0, 45.0];
+
+// CMYK 네 잉크의 덮인 비율로 RGB를 계산
+fn cmyk_to_rgb(ink: [f32; 4]) -> [f32; 3] {
+  let [c, m, y, k] = ink;
+  [
+    (1.0 - c) * (1.0 - k),
+    (1.0 - m) * (1.0 - k),
+    (1.0 - y) * (1.0 - k),
+  ]
+}
+
+fn rgb_to_cmyk(p: &[f32; 4]) -> [f32; 4] {
+  let (r, g, b) = (
+    p[0].clamp(0.0, 1.0),
+    p[1].clamp(0.0, 1.0),
+    p[2].clamp(0.0, 1.0),
+  );
+  let k = 1.0 - r.max(g).max(b);
+  if k >= 1.0 {
+    return [0.0, 0.0, 0.0, 1.0];
+  }
+  let scale = 1.0 / (1.0 - k);
+  [
+    (1.0 - r - k) * scale,
+    (1.0 - g - k) * scale,
+    (1.0 - b - k) * scale,
+    k,
+  ]
+}
+
+impl Image {
+  // 휘도로 만든 구조 텐서 (E, F, G) = (gx², gx·gy, gy²)를 sigma로 평활화합니다.
+  fn structure_tensor(&self, sigma: f32) -> Image {
+    let mut luma = Image::new(self.width, self.height);
+    for (l, pixel) in luma.pixels.iter_mut().zip(&self.pixels) {
+      *l = [luminance(pixel); 4];
+    }
+
+    // Sobel 커널은 미분 [-1, 0, 1]과 평활화 [1, 2, 1]로 분리됩니다.
+    let mut gx = luma.clone();
+    gx.as_view_mut().convolve_rows(&[-0.5, 0.0, 0.5]);
+    gx.as_view_mut().convolve_columns(&[0.25, 0.5, 0.25]);
+    let mut gy = luma;
+    gy.as_view_mut().convolve_rows(&[0.25, 0.5, 0.25]);
+    gy.as_view_mut().convolve_columns(&[-0.5, 0.0, 0.5]);
+
+    let mut tensor = gx;
+    for (t, g) in tensor.pixels.iter_mut().zip(&gy.pixels) {
+      let (x, y) = (t[0], g[0]);
+      *t = [x * x, x * y, y * y, 0.0];
+    }
+    tensor.gaussian_blur_sigma(sigma);
+    tensor
+  }
+
+  // 이방성 쿠와하라 필터 (Kyprianidis et al.)
+  // 구조 텐서로 국소 방향과 이방성을 구하고, 그 방향으로 늘린 타원을 부채꼴로 나눠
+  // 분산이 작은 부채꼴의 평균을 더 크게 반영합니다. 경계는 유지하고 평평한 영역은 붓질처럼 뭉갭니다.
+  pub fn kuwahara(&self, radius: f32, sharpness: f32) -> Image {
+    let tensor = self.structure_tensor(2.0);
+    let mut output = Image::new(self.width, self.height);
+
+    for j in 0..self.height as i32 {
+      for i in 0..self.width as i32 {
+        let [e, f, g, _] = *tensor.get_pixel(i, j);
+
+        // 구조 텐서의 고유값과 국소 방향
+        let root = ((e - g).powi(2) + 4.0 * f * f).sqrt();
+        let (l1, l2) = ((e + g + root) * 0.5, (e + g - root) * 0.5);
+        let anisotropy = if l1 + l2 > 1e-8 {
+          (l1 - l2) / (l1 + l2)
+        } else {
+          0.0
+        };
+        // 고유값이 작은 쪽의 고유벡터 (λ1 - E, -F)가 경계를 따라가는 방향입니다.
+        let angle = if (l1 - e).abs() + f.abs() > 1e-8 {
+          (-f).atan2(l1 - e)
+        } else {
+          PI / 2.0
+        };
+
+        // 방향을 따라 길고 가로질러 짧은 타원
+        let a = radius * (1.0 + anisotropy);
+        let b = radius / (1.0 + anisotropy);
+        let (sin, cos) = angle.sin_cos();
+        let extent = a.ceil() as i32;
+
+        let mut sums = [[0.0f32; 3]; KUWAHARA_SECTORS];
+        let mut squares = [[0.0f32; 3]; KUWAHARA_SECTORS];
+        let mut counts = [0.0f32; KUWAHARA_SECTORS];
+        for dy in -extent..=extent {
+          for dx in -extent..=extent {
+            // 타원 좌표를 단위 원으로 변환
+            let u = (cos * dx as f32 + sin * dy as f32) / a;
+            let v = (-sin * dx as f32 + cos * dy as f32) / b;
+            let r2 = u * u + v * v;
+            if r2 > 1.0 {
+              continue;
+            }
+
+            let pixel = self.get_pixel(i + dx, j + dy);
+            let weight = (-2.0 * r2).exp();
+            let sector_angle = v.atan2(u).rem_euclid(TAU);
+            let sector =
+              ((sector_angle / TAU * KUWAHARA_SECTORS as f32) as usize).min(KUWAHARA_SECTORS - 1);
+            // 가운데 픽셀은 모든 부채꼴에 포함시킵니다.
+            let sectors = if dx == 0 && dy == 0 {
+              0..KUWAHARA_SECTORS
+            } else {
+              sector..sector + 1
+            };
+            for s in sectors {
+              for c in 0..3 {
+                sums[s][c] += weight * pixel[c];
+                squares[s][c] += weight * pixel[c] * pixel[c];
+              }
+              counts[s] += weight;
+            }
+          }
+        }
+
+        let mut color = [0.0; 3];
+        let mut total = 0.0;
+        for s in 0..KUWAHARA_SECTORS {
+          let mean = sums[s].map(|v| v / counts[s]);
+          let variance: f32 = (0..3)
+            .map(|c| (squares[s][c] / counts[s] - mean[c] * mean[c]).max(0.0))
+            .sum();
+          let w = 1.0 / (1.0 + (variance * 255.0).powf(sharpness * 0.5));
+          for c in 0..3 {
+            color[c] += w * mean[c];
+          }
+          total += w;
+        }
+
+        let alpha = self.get_pixel(i, j)[3];
+        *output.get_pixel_mut(i, j) = [color[0] / total, color[1] / total, color[2] / total, alpha];
+      }
+    }
+    output
+  }
+
+  // 유화 효과: 주변에서 가장 많이 나타나는 밝기 단계의 평균 색으로 칠합니다.
+  // 단계마다 (색 × 포함 여부, 포함 여부)를 박스 블러해서 주변의 개수와 색 합을 구합니다.
+  pub fn oil_paint(&self, radius: u32, levels: u32) -> Image {
+    let levels = levels.max(1);
+    let level_of =
+      |p: &[f32; 4]| ((luminance(p).clamp(0.0, 1.0) * levels as f32) as u32).min(levels - 1);
+
+    let mut best_count = vec![-1.0f32; self.pixels.len()];
+    let mut output = self.clone();
+    for level in 0..levels {
+      let mut layer = Image::new(self.width, self.height);
+      for (l, pixel) in layer.pixels.iter_mut().zip(&self.pixels) {
+        if level_of(pixel) == level {
+          *l = [pixel[0], pixel[1], pixel[2], 1.0];
+        }
+      }
+      layer.box_blur(radius);
+
+      for (k, l) in layer.pixels.iter().enumerate() {
+        if l[3] > best_count[k] + 1e-6 {
+          best_count[k] = l[3];
+          let alpha = self.pixels[k][3];
+          output.pixels[k] = [l[0] / l[3], l[1] / l[3], l[2] / l[3], alpha];
+        }
+      }
+    }
+    output
+  }
+
+  // CMYK 망점 스크리닝: 채널마다 다른 각도로 회전한 격자에서, 격자 칸의 평균 잉크 양에
+  // 비례하는 넓이의 원을 찍습니다. 칸 평균은 칸 크기만큼의 가우시안 블러로 근사합니다.
+  pub fn halftone(&self, cell_size: f32) -> Image {
+    assert!(cell_size > 0.0, "Cell size must be positive");
+    let mut averaged = self.clone();
+    averaged.gaussian_blur_sigma(cell_size * 0.5);
+
+    let mut output = Image::new(self.width, self.height);
+    for j in 0..self.height as i32 {
+      for i in 0..self.width as i32 {
+        let mut ink = [0.0; 4];
+        for (channel, degrees) in SCREEN_ANGLES.iter().enumerate() {
+          let (sin, cos) = degrees.to_radians().sin_cos();
+          let (x, y) = (i as f32 + 0.5, j as f32 + 0.5);
+
+          // 회전한 격자에서 가장 가까운 칸의 중심
+          let u = cos * x + sin * y;
+          let v = -sin * x + cos * y;
+          let (cu, cv) = (
+            ((u / cell_size).floor() + 0.5) * cell_size,
+            ((v / cell_size).floor() + 0.5) * cell_size,
+          );
+          let (cx, cy) = (cos * cu - sin * cv, sin * cu + cos * cv);
+
+          // 블러의 반올림 오차로 생기는 아주 작은 점은 찍지 않습니다.
+          let coverage = rgb_to_cmyk(averaged.get_pixel(cx as i32, cy as i32))[channel];
+          let coverage = if coverage < 0.5 / 255.0 {
+            0.0
+          } else {
+            coverage
+          };
+          // 원의 넓이 πr² = coverage × 칸 넓이
+          let dot_radius = cell_size * (coverage / PI).sqrt();
+          let distance = ((u - cu).powi(2) + (v - cv).powi(2)).sqrt();
+          // 경계 1픽셀을 안티에일리어싱하되, 아주 작은 점이 커 보이지 않도록 제한합니다.
+          ink[channel] = (dot_radius - distance + 0.5)
+            .clamp(0.0, 1.0)
+            .min(dot_radius * 2.0);
+        }
+
+        let [r, g, b] = cmyk_to_rgb(ink);
+        *output.get_pixel_mut(i, j) = [r, g, b, self.get_pixel(i, j)[3]];
+      }
+    }
+    output
+  }
+
+  // block_size x block_size 블록을 블록의 평균 색으로 칠합니다.
+  pub fn pixelate(&mut self, block_size: u32) {
+    let block_size = block_size.max(1);
+    for y in (0..self.height).step_by(block_size as usize) {
+      for x in (0..self.width).step_by(block_size as usize) {
+        let (w, h) = (
+          block_size.min(self.width - x),
+          block_size.min(self.height - y),
+        );
+        let mean = self.view(x, y, w, h).mean();
+        self.view_mut(x, y, w, h).fill(mean);
+      }
+    }
+  }
+
+  // 채널마다 levels 단계로 양자화 (알파 제외)
+  pub fn posterize(&mut self, levels: u32) {
+    let steps = (levels.max(2) - 1) as f32;
+    for pixel in self.pixels.iter_mut() {
+      for v in pixel.iter_mut().take(3) {
+        *v = (v.clamp(0.0, 1.0) * steps).round() / steps;
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::image::noise::Rng;
+
+  fn noisy_edge(seed: u64) -> Image {
+    let mut rng = Rng::new(seed);
+    let mut image = Image::new(24, 24);
+    for j in 0..24 {
+      for i in 0..24 {
+        let base = if i < 12 { 0.2 } else { 0.8 };
+        let v = base + (rng.next_f32() - 0.5) * 0.1;
+        *image.get_pixel_mut(i, j) = [v, v, v, 1.0];
+      }
+    }
+    image
+  }
+
+  fn variance(values: impl Iterator<Item = f32>) -> f32 {
+    let values: Vec<f32> = values.collect();
+    let mean = values.iter().sum::<f32>() / values.len() as f32;
+    values.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / values.len() as f32
+  }
+
+  #[test]
+  fn test_kuwahara_smooths_but_keeps_edges() {
+    let image = noisy_edge(1);
+    let filtered = image.kuwahara(4.0, 8.0);
+
+    // 평평한 영역의 노이즈는 줄어듭니다.
+    let region = |img: &Image| {
+      variance(
+        (4..8)
+          .flat_map(|j| (2..8).map(move |i| (i, j)))
+          .map(|(i, j)| img.get_pixel(i, j)[0]),
+      )
+    };
+    assert!(region(&filtered) < region(&image) * 0.5);
+
+    // 경계 양쪽의 값은 섞이지 않습니다.
+    assert!((filtered.get_pixel(10, 12)[0] - 0.2).abs() < 0.1);
+    assert!((filtered.get_pixel(13, 12)[0] - 0.8).abs() < 0.1);
+  }
+
+  #[test]
+  fn test_oil_paint_uses_dominant_level() {
+    let image = noisy_edge(2);
+    let painted = image.oil_paint(2, 4);
+    assert!((painted.get_pixel(3, 3)[0] - 0.2).abs() < 0.05);
+    assert!((painted.get_pixel(20, 3)[0] - 0.8).abs() < 0.05);
+
+    let mut flat = Image::new(6, 6);
+    flat.as_view_mut().fill([0.3, 0.4, 0.5, 1.0]);
+    for (a, b) in flat.oil_paint(2, 8).pixels.iter().zip(&flat.pixels) {
+      assert!(a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-5));
+    }
+  }
+
+  #[test]
+  #[should_panic(expected = "Cell size must be positive")]
+  fn test_halftone_zero_cell_size() {
+    Image::new(4, 4).halftone(0.0);
+  }
+
+  #[test]
+  fn test_halftone_coverage() {
+    let mut white = Image::new(16, 16);
+    white.as_view_mut().fill([1.0; 4]);
+    assert!(white.halftone(4.0).pixels.iter().all(|p| p[0] == 1.0));
+
+    // 50% 회색은 평균 밝기가 대략 절반이 됩니다.
+    let mut gray = Image::new(32, 32);
+    gray.as_view_mut().fill([0.5, 0.5, 0.5, 1.0]);
+    let mean = gray.halftone(4.0).as_view().mean();
+    assert!((mean[0] - 0.5).abs() < 0.15, "{}", mean[0]);
+    assert_eq!(rgb_to_cmyk(&[0.5, 0.5, 0.5, 1.0]), [0.0, 0.0, 0.0, 0.5]);
+  }
+
+  #[test]
+  fn test_pixelate_and_posterize() {
+    let mut image = Image::linear_gradient(6, 2, [0.0; 4], [1.0; 4]);
+    image.pixelate(4);
+    assert_eq!(image.get_pixel(0, 0), image.get_pixel(3, 1));
+    assert!((image.get_pixel(0, 0)[0] - 0.3).abs() < 1e-5);
+    // 오른쪽의 불완전한 블록
+    assert!((image.get_pixel(5, 0)[0] - 0.9).abs() < 1e-5);
+
+    let mut image = Image::linear_gradient(5, 1, [0.0; 4], [1.0; 4]);
+    image.posterize(3);
+    let values: Vec<f32> = image.pixels.iter().map(|p| p[0]).collect();
+    assert_eq!(values, vec![0.0, 0.5, 0.5, 1.0, 1.0]);
+    assert_eq!(image.get_pixel(1, 0)[3], 0.25);
+  }
+}
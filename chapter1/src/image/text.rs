@@ -0,0 +1,295 @@
+use super::Image;
+
+pub const GLYPH_SIZE: u32 = 8;
+
+// ASCII 32(' ')부터 126('~')까지의 8x8 비트맵 폰트
+// 각 바이트가 한 행이며 최하위 비트가 가장 왼쪽 픽셀입니다.
+// 출처: Daniel Hepper의 font8x8 (퍼블릭 도메인, IBM PC BIOS 폰트 기반)
+const FONT8X8: [[u8; 8]; 95] = [
+  [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+  [0x18, 0x3C, 0x3C, 0x18, 0x18, 0x00, 0x18, 0x00], // '!'
+  [0x36, 0x36, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // '"'
+  [0x36, 0x36, 0x7F, 0x36, 0x7F, 0x36, 0x36, 0x00], // '#'
+  [0x0C, 0x3E, 0x03, 0x1E, 0x30, 0x1F, 0x0C, 0x00], // '$'
+  [0x00, 0x63, 0x33, 0x18, 0x0C, 0x66, 0x63, 0x00], // '%'
+  [0x1C, 0x36, 0x1C, 0x6E, 0x3B, 0x33, 0x6E, 0x00], // '&'
+  [0x06, 0x06, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00], // '\''
+  [0x18, 0x0C, 0x06, 0x06, 0x06, 0x0C, 0x18, 0x00], // '('
+  [0x06, 0x0C, 0x18, 0x18, 0x18, 0x0C, 0x06, 0x00], // ')'
+  [0x00, 0x66, 0x3C, 0xFF, 0x3C, 0x66, 0x00, 0x00], // '*'
+  [0x00, 0x0C, 0x0C, 0x3F, 0x0C, 0x0C, 0x00, 0x00], // '+'
+  [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x06], // ','
+  [0x00, 0x00, 0x00, 0x3F, 0x00, 0x00, 0x00, 0x00], // '-'
+  [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x00], // '.'
+  [0x60, 0x30, 0x18, 0x0C, 0x06, 0x03, 0x01, 0x00], // '/'
+  [0x3E, 0x63, 0x73, 0x7B, 0x6F, 0x67, 0x3E, 0x00], // '0'
+  [0x0C, 0x0E, 0x0C, 0x0C, 0x0C, 0x0C, 0x3F, 0x00], // '1'
+  [0x1E, 0x33, 0x30, 0x1C, 0x06, 0x33, 0x3F, 0x00], // '2'
+  [0x1E, 0x33, 0x30, 0x1C, 0x30, 0x33, 0x1E, 0x00], // '3'
+  [0x38, 0x3C, 0x36, 0x33, 0x7F, 0x30, 0x78, 0x00], // '4'
+  [0x3F, 0x03, 0x1F, 0x30, 0x30, 0x33, 0x1E, 0x00], // '5'
+  [0x1C, 0x06, 0x03, 0x1F, 0x33, 0x33, 0x1E, 0x00], // '6'
+  [0x3F, 0x33, 0x30, 0x18, 0x0C, 0x0C, 0x0C, 0x00], // '7'
+  [0x1E, 0x33, 0x33, 0x1E, 0x33, 0x33, 0x1E, 0x00], // '8'
+  [0x1E, 0x33, 0x33, 0x3E, 0x30, 0x18, 0x0E, 0x00], // '9'
+  [0x00, 0x0C, 0x0C, 0x00, 0x00, 0x0C, 0x0C, 0x00], // ':'
+  [0x00, 0x0C, 0x0C, 0x00, 0x00, 0x0C, 0x0C, 0x06], // ';'
+  [0x18, 0x0C, 0x06, 0x03, 0x06, 0x0C, 0x18, 0x00], // '<'
+  [0x00, 0x00, 0x3F, 0x00, 0x00, 0x3F, 0x00, 0x00], // '='
+  [0x06, 0x0C, 0x18, 0x30, 0x18, 0x0C, 0x06, 0x00], // '>'
+  [0x1E, 0x33, 0x30, 0x18, 0x0C, 0x00, 0x0C, 0x00], // '?'
+  [0x3E, 0x63, 0x7B, 0x7B, 0x7B, 0x03, 0x1E, 0x00], // '@'
+  [0x0C, 0x1E, 0x33, 0x33, 0x3F, 0x33, 0x33, 0x00], // 'A'
+  [0x3F, 0x66, 0x66, 0x3E, 0x66, 0x66, 0x3F, 0x00], // 'B'
+  [0x3C, 0x66, 0x03, 0x03, 0x03, 0x66, 0x3C, 0x00], // 'C'
+  [0x1F, 0x36, 0x66, 0x66, 0x66, 0x36, 0x1F, 0x00], // 'D'
+  [0x7F, 0x46, 0x16, 0x1E, 0x16, 0x46, 0x7F, 0x00], // 'E'
+  [0x7F, 0x46, 0x16, 0x1E, 0x16, 0x06, 0x0F, 0x00], // 'F'
+  [0x3C, 0x66, 0x03, 0x03, 0x73, 0x66, 0x7C, 0x00], // 'G'
+  [0x33, 0x33, 0x33, 0x3F, 0x33, 0x33, 0x33, 0x00], // 'H'
+  [0x1E, 0x0C, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // 'I'
+  [0x78, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1E, 0x00], // 'J'
+  [0x67, 0x66, 0x36, 0x1E, 0x36, 0x66, 0x67, 0x00], // 'K'
+  [0x0F, 0x06, 0x06, 0x06, 0x46, 0x66, 0x7F, 0x00], // 'L'
+  [0x63, 0x77, 0x7F, 0x7F, 0x6B, 0x63, 0x63, 0x00], // 'M'
+  [0x63, 0x67, 0x6F, 0x7B, 0x73, 0x63, 0x63, 0x00], // 'N'
+  [0x1C, 0x36, 0x63, 0x63, 0x63, 0x36, 0x1C, 0x00], // 'O'
+  [0x3F, 0x66, 0x66, 0x3E, 0x06, 0x06, 0x0F, 0x00], // 'P'
+  [0x1E, 0x33, 0x33, 0x33, 0x3B, 0x1E, 0x38, 0x00], // 'Q'
+  [0x3F, 0x66, 0x66, 0x3E, 0x36, 0x66, 0x67, 0x00], // 'R'
+  [0x1E, 0x33, 0x07, 0x0E, 0x38, 0x33, 0x1E, 0x00], // 'S'
+  [0x3F, 0x2D, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // 'T'
+  [0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x3F, 0x00], // 'U'
+  [0x33, 0x33, 0x33, 0x33, 0x33, 0x1E, 0x0C, 0x00], // 'V'
+  [0x63, 0x63, 0x63, 0x6B, 0x7F, 0x77, 0x63, 0x00], // 'W'
+  [0x63, 0x63, 0x36, 0x1C, 0x1C, 0x36, 0x63, 0x00], // 'X'
+  [0x33, 0x33, 0x33, 0x1E, 0x0C, 0x0C, 0x1E, 0x00], // 'Y'
+  [0x7F, 0x63, 0x31, 0x18, 0x4C, 0x66, 0x7F, 0x00], // 'Z'
+  [0x1E, 0x06, 0x06, 0x06, 0x06, 0x06, 0x1E, 0x00], // '['
+  [0x03, 0x06, 0x0C, 0x18, 0x30, 0x60, 0x40, 0x00], // '\\'
+  [0x1E, 0x18, 0x18, 0x18, 0x18, 0x18, 0x1E, 0x00], // ']'
+  [0x08, 0x1C, 0x36, 0x63, 0x00, 0x00, 0x00, 0x00], // '^'
+  [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF], // '_'
+  [0x0C, 0x0C, 0x18, 0x00, 0x00, 0x00, 0x00, 0x00], // '`'
+  [0x00, 0x00, 0x1E, 0x30, 0x3E, 0x33, 0x6E, 0x00], // 'a'
+  [0x07, 0x06, 0x06, 0x3E, 0x66, 0x66, 0x3B, 0x00], // 'b'
+  [0x00, 0x00, 0x1E, 0x33, 0x03, 0x33, 0x1E, 0x00], // 'c'
+  [0x38, 0x30, 0x30, 0x3E, 0x33, 0x33, 0x6E, 0x00], // 'd'
+  [0x00, 0x00, 0x1E, 0x33, 0x3F, 0x03, 0x1E, 0x00], // 'e'
+  [0x1C, 0x36, 0x06, 0x0F, 0x06, 0x06, 0x0F, 0x00], // 'f'
+  [0x00, 0x00, 0x6E, 0x33, 0x33, 0x3E, 0x30, 0x1F], // 'g'
+  [0x07, 0x06, 0x36, 0x6E, 0x66, 0x66, 0x67, 0x00], // 'h'
+  [0x0C, 0x00, 0x0E, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // 'i'
+  [0x30, 0x00, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1E], // 'j'
+  [0x07, 0x06, 0x66, 0x36, 0x1E, 0x36, 0x67, 0x00], // 'k'
+  [0x0E, 0x0C, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // 'l'
+  [0x00, 0x00, 0x33, 0x7F, 0x7F, 0x6B, 0x63, 0x00], // 'm'
+  [0x00, 0x00, 0x1F, 0x33, 0x33, 0x33, 0x33, 0x00], // 'n'
+  [0x00, 0x00, 0x1E, 0x33, 0x33, 0x33, 0x1E, 0x00], // 'o'
+  [0x00, 0x00, 0x3B, 0x66, 0x66, 0x3E, 0x06, 0x0F], // 'p'
+  [0x00, 0x00, 0x6E, 0x33, 0x33, 0x3E, 0x30, 0x78], // 'q'
+  [0x00, 0x00, 0x3B, 0x6E, 0x66, 0x06, 0x0F, 0x00], // 'r'
+  [0x00, 0x00, 0x3E, 0x03, 0x1E, 0x30, 0x1F, 0x00], // 's'
+  [0x08, 0x0C, 0x3E, 0x0C, 0x0C, 0x2C, 0x18, 0x00], // 't'
+  [0x00, 0x00, 0x33, 0x33, 0x33, 0x33, 0x6E, 0x00], // 'u'
+  [0x00, 0x00, 0x33, 0x33, 0x33, 0x1E, 0x0C, 0x00], // 'v'
+  [0x00, 0x00, 0x63, 0x6B, 0x7F, 0x7F, 0x36, 0x00], // 'w'
+  [0x00, 0x00, 0x63, 0x36, 0x1C, 0x36, 0x63, 0x00], // 'x'
+  [0x00, 0x00, 0x33, 0x33, 0x33, 0x3E, 0x30, 0x1F], // 'y'
+  [0x00, 0x00, 0x3F, 0x19, 0x0C, 0x26, 0x3F, 0x00], // 'z'
+  [0x38, 0x0C, 0x0C, 0x07, 0x0C, 0x0C, 0x38, 0x00], // '{'
+  [0x18, 0x18, 0x18, 0x00, 0x18, 0x18, 0x18, 0x00], // '|'
+  [0x07, 0x0C, 0x0C, 0x38, 0x0C, 0x0C, 0x07, 0x00], // '}'
+  [0x6E, 0x3B, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // '~'
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextAlign {
+  Left,
+  Center,
+  Right,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct TextStyle {
+  pub color: [f32; 4],
+  // 글리프 한 픽셀을 scale x scale 블록으로 확대
+  pub scale: u32,
+  pub align: TextAlign,
+  // 줄 사이에 추가로 띄울 픽셀 수 (scale 적용 전)
+  pub line_spacing: u32,
+}
+
+impl Default for TextStyle {
+  fn default() -> Self {
+    Self {
+      color: [1.0, 1.0, 1.0, 1.0],
+      scale: 1,
+      align: TextAlign::Left,
+      line_spacing: 2,
+    }
+  }
+}
+
+impl TextStyle {
+  // scale이 0이면 1로 그립니다.
+  fn pixel_scale(&self) -> u32 {
+    self.scale.max(1)
+  }
+
+  fn line_height(&self) -> u32 {
+    (GLYPH_SIZE + self.line_spacing) * self.pixel_scale()
+  }
+
+  fn line_width(&self, line: &str) -> u32 {
+    line.chars().count() as u32 * GLYPH_SIZE * self.pixel_scale()
+  }
+
+  // 여러 줄 텍스트가 차지하는 (너비, 높이)
+  pub fn measure(&self, text: &str) -> (u32, u32) {
+    let width = text_lines(text).map(|line| self.line_width(line)).max();
+    let lines = text_lines(text).count() as u32;
+    let height = lines * self.line_height() - self.line_spacing * self.pixel_scale();
+    (width.unwrap_or(0), height)
+  }
+}
+
+// '\n'으로 줄을 나누고 CRLF의 '\r'은 떼어냅니다. (끝의 빈 줄도 한 줄로 셉니다)
+fn text_lines(text: &str) -> impl Iterator<Item = &str> {
+  text
+    .split('\n')
+    .map(|line| line.strip_suffix('\r').unwrap_or(line))
+}
+
+// 폰트에 없는 문자는 '?'로 표시
+fn glyph(c: char) -> &'static [u8; 8] {
+  let index = match c {
+    ' '..='~' => c as usize - ' ' as usize,
+    '\t' => 0,
+    _ => '?' as usize - ' ' as usize,
+  };
+  &FONT8X8[index]
+}
+
+impl Image {
+  // (x, y)는 첫 줄의 왼쪽 위 기준점이며, 정렬에 따라 각 줄의 시작/가운데/끝이 x에 맞춰집니다.
+  pub fn draw_text(&mut self, x: i32, y: i32, text: &str, style: &TextStyle) {
+    let scale = style.pixel_scale() as i32;
+
+    for (row, line) in text_lines(text).enumerate() {
+      let line_width = style.line_width(line) as i32;
+      let line_x = match style.align {
+        TextAlign::Left => x,
+        TextAlign::Center => x - line_width / 2,
+        TextAlign::Right => x - line_width,
+      };
+      let line_y = y + row as i32 * style.line_height() as i32;
+
+      for (column, c) in line.chars().enumerate() {
+        let glyph_x = line_x + column as i32 * GLYPH_SIZE as i32 * scale;
+        self.draw_glyph(glyph_x, line_y, glyph(c), style.color, scale);
+      }
+    }
+  }
+
+  fn draw_glyph(&mut self, x: i32, y: i32, bits: &[u8; 8], color: [f32; 4], scale: i32) {
+    for (gy, row) in bits.iter().enumerate() {
+      for gx in 0..GLYPH_SIZE as i32 {
+        if row & (1 << gx) != 0 {
+          let px = x + gx * scale;
+          let py = y + gy as i32 * scale;
+          self.fill_rect(px, py, scale, scale, color);
+        }
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_glyph_bits() {
+    let mut image = Image::new(8, 8);
+    image.draw_text(0, 0, "A", &TextStyle::default());
+
+    for (j, row) in glyph('A').iter().enumerate() {
+      for i in 0..8 {
+        let lit = image.get_pixel(i, j as i32)[3] > 0.0;
+        assert_eq!(lit, row & (1 << i) != 0);
+      }
+    }
+  }
+
+  #[test]
+  fn test_measure_multiline() {
+    let style = TextStyle {
+      scale: 2,
+      ..Default::default()
+    };
+    assert_eq!(style.measure("frame 12\nok"), (8 * 8 * 2, (8 + 2 + 8) * 2));
+    assert_eq!(style.measure(""), (0, 16));
+  }
+
+  #[test]
+  fn test_crlf_matches_lf() {
+    let style = TextStyle::default();
+    assert_eq!(style.measure("AB\r\nCD\r\n"), style.measure("AB\nCD\n"));
+
+    let mut crlf = Image::new(24, 24);
+    let mut lf = Image::new(24, 24);
+    crlf.draw_text(0, 0, "AB\r\nCD", &style);
+    lf.draw_text(0, 0, "AB\nCD", &style);
+    assert_eq!(crlf.pixels, lf.pixels);
+  }
+
+  #[test]
+  fn test_measure_matches_drawn_bounds() {
+    for scale in [0, 1, 3] {
+      let style = TextStyle {
+        scale,
+        align: TextAlign::Center,
+        ..Default::default()
+      };
+      let s = style.pixel_scale() as i32;
+      let (w, h) = style.measure("AB\nCD");
+      let mut image = Image::new(w + 8, h + 8);
+      image.draw_text(w as i32 / 2, 0, "AB\nCD", &style);
+
+      let lit: Vec<(i32, i32)> = (0..image.height as i32)
+        .flat_map(|j| (0..image.width as i32).map(move |i| (i, j)))
+        .filter(|&(i, j)| image.get_pixel(i, j)[3] > 0.0)
+        .collect();
+      let max_x = lit.iter().map(|p| p.0).max().unwrap();
+      let max_y = lit.iter().map(|p| p.1).max().unwrap();
+      // 글리프는 칸 안쪽에 그려지므로 마지막 칸 안에서 끝납니다.
+      assert!(
+        max_x < w as i32 && max_x >= w as i32 - 8 * s,
+        "scale {scale}"
+      );
+      assert!(
+        max_y < h as i32 && max_y >= h as i32 - 8 * s,
+        "scale {scale}"
+      );
+    }
+  }
+
+  #[test]
+  fn test_right_align_and_blend() {
+    let style = TextStyle {
+      color: [1.0, 0.0, 0.0, 0.5],
+      align: TextAlign::Right,
+      ..Default::default()
+    };
+    let mut image = Image::new(32, 10);
+    image.draw_text(32, 0, "|", &style);
+
+    // '|'는 가운데 두 열에 그려지므로 오른쪽 끝 8픽셀 안에 있어야 합니다.
+    let lit: Vec<i32> = (0..32)
+      .filter(|&i| image.get_pixel(i, 2)[3] > 0.0)
+      .collect();
+    assert!(!lit.is_empty() && lit.iter().all(|&i| i >= 24));
+    assert_eq!(image.get_pixel(lit[0], 2), &[0.5, 0.0, 0.0, 0.5]);
+  }
+}
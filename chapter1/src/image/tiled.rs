@@ -0,0 +1,507 @@
+use super::{to_u8, Image};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+// 전체 이미지를 메모리에 올리지 않고 한 행씩 읽고 쓰기 위한 인터페이스
+pub trait RowSource {
+  fn width(&self) -> u32;
+  fn height(&self) -> u32;
+  fn read_row(&mut self, row: &mut [[f32; 4]]) -> io::Result<()>;
+}
+
+pub trait RowSink {
+  fn write_row(&mut self, row: &[[f32; 4]]) -> io::Result<()>;
+
+  // 마지막 행을 쓴 뒤 남은 데이터를 내보냅니다.
+  fn finish(&mut self) -> io::Result<()> {
+    Ok(())
+  }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+  io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+// 바이너리 PGM(P5), PPM(P6) 스트리밍 리더 (8비트, 16비트)
+pub struct NetpbmRowReader<R: BufRead> {
+  reader: R,
+  width: u32,
+  height: u32,
+  channels: usize,
+  max_value: u32,
+  buffer: Vec<u8>,
+}
+
+impl<R: BufRead> NetpbmRowReader<R> {
+  pub fn new(mut reader: R) -> io::Result<Self> {
+    let magic = read_header_token(&mut reader)?;
+    let channels = match magic.as_str() {
+      "P5" => 1,
+      "P6" => 3,
+      _ => return Err(invalid_data("Only binary P5/P6 files can be streamed")),
+    };
+
+    let mut number = || -> io::Result<u32> {
+      read_header_token(&mut reader)?
+        .parse()
+        .map_err(|_| invalid_data("Invalid number in Netpbm header"))
+    };
+    let width = number()?;
+    let height = number()?;
+    let max_value = number()?;
+    if !(1..=65535).contains(&max_value) {
+      return Err(invalid_data("Invalid Netpbm maxval"));
+    }
+
+    let sample_size = if max_value > 255 { 2 } else { 1 };
+    Ok(Self {
+      reader,
+      width,
+      height,
+      channels,
+      max_value,
+      buffer: vec![0; width as usize * channels * sample_size],
+    })
+  }
+}
+
+// 공백과 주석을 건너뛰고 토큰 하나를 읽습니다. 토큰 뒤의 공백 한 글자도 소비합니다.
+fn read_header_token(reader: &mut impl BufRead) -> io::Result<String> {
+  let mut token = String::new();
+  let mut byte = [0u8];
+  let mut in_comment = false;
+
+  loop {
+    reader.read_exact(&mut byte)?;
+    let c = byte[0];
+    if in_comment {
+      in_comment = c != b'\n';
+    } else if c == b'#' && token.is_empty() {
+      in_comment = true;
+    } else if c.is_ascii_whitespace() {
+      if !token.is_empty() {
+        return Ok(token);
+      }
+    } else {
+      token.push(c as char);
+    }
+  }
+}
+
+impl<R: BufRead> RowSource for NetpbmRowReader<R> {
+  fn width(&self) -> u32 {
+    self.width
+  }
+
+  fn height(&self) -> u32 {
+    self.height
+  }
+
+  fn read_row(&mut self, row: &mut [[f32; 4]]) -> io::Result<()> {
+    self.reader.read_exact(&mut self.buffer)?;
+
+    let scale = 1.0 / self.max_value as f32;
+    let sample_size = self.buffer.len() / (self.width as usize * self.channels);
+    for (pixel, bytes) in row
+      .iter_mut()
+      .zip(self.buffer.chunks_exact(self.channels * sample_size))
+    {
+      let sample = |c: usize| {
+        let s = &bytes[c * sample_size..(c + 1) * sample_size];
+        s.iter().fold(0u32, |acc, &b| (acc << 8) | b as u32) as f32 * scale
+      };
+      *pixel = if self.channels == 1 {
+        let v = sample(0);
+        [v, v, v, 1.0]
+      } else {
+        [sample(0), sample(1), sample(2), 1.0]
+      };
+    }
+    Ok(())
+  }
+}
+
+// 바이너리 PPM(P6) 8비트 스트리밍 라이터
+pub struct NetpbmRowWriter<W: Write> {
+  writer: W,
+  buffer: Vec<u8>,
+}
+
+impl<W: Write> NetpbmRowWriter<W> {
+  pub fn new(mut writer: W, width: u32, height: u32) -> io::Result<Self> {
+    write!(writer, "P6\n{} {}\n255\n", width, height)?;
+    Ok(Self {
+      writer,
+      buffer: Vec::with_capacity(width as usize * 3),
+    })
+  }
+}
+
+impl<W: Write> RowSink for NetpbmRowWriter<W> {
+  fn write_row(&mut self, row: &[[f32; 4]]) -> io::Result<()> {
+    self.buffer.clear();
+    self
+      .buffer
+      .extend(row.iter().flat_map(|p| [p[0], p[1], p[2]].map(to_u8)));
+    self.writer.write_all(&self.buffer)
+  }
+
+  fn finish(&mut self) -> io::Result<()> {
+    self.writer.flush()
+  }
+}
+
+// PNG 스트리밍 리더 (팔레트와 8비트 미만의 회색조는 8비트로 확장해서 읽습니다)
+pub struct PngRowReader<R: Read> {
+  reader: png::Reader<R>,
+}
+
+impl<R: Read> PngRowReader<R> {
+  pub fn new(reader: R) -> io::Result<Self> {
+    let mut decoder = png::Decoder::new(reader);
+    decoder.set_transformations(png::Transformations::EXPAND);
+    let reader = decoder.read_info()?;
+
+    // 인터레이스 PNG는 행 순서대로 읽을 수 없습니다.
+    if reader.info().interlaced {
+      return Err(invalid_data("Interlaced PNG cannot be streamed"));
+    }
+    Ok(Self { reader })
+  }
+}
+
+impl<R: Read> RowSource for PngRowReader<R> {
+  fn width(&self) -> u32 {
+    self.reader.info().width
+  }
+
+  fn height(&self) -> u32 {
+    self.reader.info().height
+  }
+
+  fn read_row(&mut self, row: &mut [[f32; 4]]) -> io::Result<()> {
+    let (color_type, bit_depth) = self.reader.output_color_type();
+    let data = self
+      .reader
+      .next_row()?
+      .ok_or_else(|| invalid_data("Unexpected end of PNG data"))?
+      .data();
+
+    let (sample_size, scale) = match bit_depth {
+      png::BitDepth::Sixteen => (2, 1.0 / 65535.0),
+      _ => (1, 1.0 / 255.0),
+    };
+    let channels = color_type.samples();
+
+    for (pixel, bytes) in row
+      .iter_mut()
+      .zip(data.chunks_exact(channels * sample_size))
+    {
+      let s = |c: usize| {
+        let b = &bytes[c * sample_size..(c + 1) * sample_size];
+        b.iter().fold(0u32, |acc, &b| (acc << 8) | b as u32) as f32 * scale
+      };
+      *pixel = match channels {
+        1 => [s(0), s(0), s(0), 1.0],
+        2 => [s(0), s(0), s(0), s(1)],
+        3 => [s(0), s(1), s(2), 1.0],
+        _ => [s(0), s(1), s(2), s(3)],
+      };
+    }
+    Ok(())
+  }
+}
+
+// RGBA 8비트 PNG 스트리밍 라이터
+pub struct PngRowWriter<W: Write + 'static> {
+  writer: Option<png::StreamWriter<'static, W>>,
+  buffer: Vec<u8>,
+}
+
+impl<W: Write + 'static> PngRowWriter<W> {
+  pub fn new(writer: W, width: u32, height: u32) -> io::Result<Self> {
+    let mut encoder = png::Encoder::new(writer, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let writer = encoder.write_header()?.into_stream_writer()?;
+
+    Ok(Self {
+      writer: Some(writer),
+      buffer: Vec::with_capacity(width as usize * 4),
+    })
+  }
+}
+
+impl<W: Write + 'static> RowSink for PngRowWriter<W> {
+  fn write_row(&mut self, row: &[[f32; 4]]) -> io::Result<()> {
+    self.buffer.clear();
+    self.buffer.extend(row.iter().flat_map(|p| p.map(to_u8)));
+    self
+      .writer
+      .as_mut()
+      .ok_or_else(|| invalid_data("PNG writer already finished"))?
+      .write_all(&self.buffer)
+  }
+
+  fn finish(&mut self) -> io::Result<()> {
+    match self.writer.take() {
+      Some(writer) => Ok(writer.finish()?),
+      None => Ok(()),
+    }
+  }
+}
+
+// 헤더 없는 RGBA f32 리틀엔디언 덤프 (Image::encode_raw_f32와 같은 형식)
+pub struct RawF32RowReader<R: Read> {
+  reader: R,
+  width: u32,
+  height: u32,
+  buffer: Vec<u8>,
+}
+
+impl<R: Read> RawF32RowReader<R> {
+  pub fn new(reader: R, width: u32, height: u32) -> Self {
+    Self {
+      reader,
+      width,
+      height,
+      buffer: vec![0; width as usize * 16],
+    }
+  }
+}
+
+impl<R: Read> RowSource for RawF32RowReader<R> {
+  fn width(&self) -> u32 {
+    self.width
+  }
+
+  fn height(&self) -> u32 {
+    self.height
+  }
+
+  fn read_row(&mut self, row: &mut [[f32; 4]]) -> io::Result<()> {
+    self.reader.read_exact(&mut self.buffer)?;
+    for (pixel, bytes) in row.iter_mut().zip(self.buffer.chunks_exact(16)) {
+      *pixel = std::array::from_fn(|c| {
+        f32::from_le_bytes([
+          bytes[c * 4],
+          bytes[c * 4 + 1],
+          bytes[c * 4 + 2],
+          bytes[c * 4 + 3],
+        ])
+      });
+    }
+    Ok(())
+  }
+}
+
+pub struct RawF32RowWriter<W: Write> {
+  writer: W,
+  buffer: Vec<u8>,
+}
+
+impl<W: Write> RawF32RowWriter<W> {
+  pub fn new(writer: W) -> Self {
+    Self {
+      writer,
+      buffer: Vec::new(),
+    }
+  }
+}
+
+impl<W: Write> RowSink for RawF32RowWriter<W> {
+  fn write_row(&mut self, row: &[[f32; 4]]) -> io::Result<()> {
+    self.buffer.clear();
+    self.buffer.extend(
+      row
+        .iter()
+        .flat_map(|p| p.iter().flat_map(|v| v.to_le_bytes())),
+    );
+    self.writer.write_all(&self.buffer)
+  }
+
+  fn finish(&mut self) -> io::Result<()> {
+    self.writer.flush()
+  }
+}
+
+// 확장자(.png, .ppm, .pgm, .pnm)에 맞는 스트리밍 리더/라이터
+pub fn open_row_source(path: &Path) -> io::Result<Box<dyn RowSource>> {
+  let reader = BufReader::new(File::open(path)?);
+  match path.extension().and_then(|e| e.to_str()) {
+    Some("png") => Ok(Box::new(PngRowReader::new(reader)?)),
+    Some("ppm" | "pgm" | "pnm") => Ok(Box::new(NetpbmRowReader::new(reader)?)),
+    _ => Err(invalid_data("Unsupported file extension for streaming")),
+  }
+}
+
+pub fn create_row_sink(path: &Path, width: u32, height: u32) -> io::Result<Box<dyn RowSink>> {
+  let writer = BufWriter::new(File::create(path)?);
+  match path.extension().and_then(|e| e.to_str()) {
+    Some("png") => Ok(Box::new(PngRowWriter::new(writer, width, height)?)),
+    Some("ppm") => Ok(Box::new(NetpbmRowWriter::new(writer, width, height)?)),
+    _ => Err(invalid_data("Unsupported file extension for streaming")),
+  }
+}
+
+// 이미지를 strip_height 행씩 잘라서 처리합니다.
+// 각 스트립의 위아래로 halo 행을 더 읽어서 이웃 픽셀을 참조하는 필터도 전체 이미지에 적용한 것과
+// 같은 결과를 얻습니다. (halo는 필터 반경의 합 이상이어야 합니다.)
+// 메모리 사용량은 약 2 * (strip_height + 2 * halo) * width * 16 바이트로 이미지 높이와 무관합니다.
+#[derive(Clone, Copy, Debug)]
+pub struct StripProcessor {
+  pub strip_height: u32,
+  pub halo: u32,
+}
+
+impl StripProcessor {
+  pub fn run(
+    &self, source: &mut dyn RowSource, sink: &mut dyn RowSink, mut filter: impl FnMut(&mut Image),
+  ) -> io::Result<()> {
+    let (width, height) = (source.width(), source.height());
+    let row_len = width as usize;
+    let strip_height = self.strip_height.max(1);
+
+    // rows는 현재 스트립의 top번째 행부터 연속된 행들을 담고 있습니다.
+    let mut rows: Vec<[f32; 4]> = Vec::new();
+    let mut carry: Vec<[f32; 4]> = Vec::new();
+    let mut next_row = 0;
+
+    for y0 in (0..height).step_by(strip_height as usize) {
+      let y1 = (y0 + strip_height).min(height);
+      let top = y0.saturating_sub(self.halo);
+      let bottom = (y1 + self.halo).min(height);
+
+      // 이전 스트립에서 넘겨받은 행 다음부터 필요한 행까지 읽기
+      while next_row < bottom {
+        let start = rows.len();
+        rows.resize(start + row_len, [0.0; 4]);
+        source.read_row(&mut rows[start..])?;
+        next_row += 1;
+      }
+
+      // 필터는 버퍼를 덮어쓰므로 다음 스트립과 겹치는 행만 원본을 남겨 둡니다.
+      let next_top = y1.saturating_sub(self.halo);
+      carry.clear();
+      carry.extend_from_slice(&rows[(next_top - top) as usize * row_len..]);
+
+      let mut strip = Image {
+        width,
+        height: bottom - top,
+        channels: 4,
+        pixels: std::mem::take(&mut rows),
+      };
+      filter(&mut strip);
+
+      for y in y0..y1 {
+        let start = (y - top) as usize * row_len;
+        sink.write_row(&strip.pixels[start..start + row_len])?;
+      }
+
+      rows = std::mem::take(&mut strip.pixels);
+      rows.clear();
+      rows.extend_from_slice(&carry);
+    }
+
+    sink.finish()
+  }
+
+  pub fn run_files(
+    &self, input: &Path, output: &Path, filter: impl FnMut(&mut Image),
+  ) -> io::Result<()> {
+    let mut source = open_row_source(input)?;
+    let mut sink = create_row_sink(output, source.width(), source.height())?;
+    self.run(source.as_mut(), sink.as_mut(), filter)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::image::netpbm::NetpbmEncoding;
+  use crate::image::noise::Noise;
+  use std::io::Cursor;
+
+  #[test]
+  fn test_strips_match_whole_image() {
+    let image = Image::noise(23, 17, Noise::Perlin, 5, 4);
+    let mut expected = image.clone();
+    expected.gaussian_blur();
+    expected.gaussian_blur();
+
+    // 5탭 가우시안을 두 번 적용하므로 halo는 2 + 2
+    let processor = StripProcessor {
+      strip_height: 3,
+      halo: 4,
+    };
+    let bytes = image.encode_raw_f32();
+    let mut source = RawF32RowReader::new(Cursor::new(bytes), 23, 17);
+    let mut output = Vec::new();
+    let mut sink = RawF32RowWriter::new(&mut output);
+    processor
+      .run(&mut source, &mut sink, |strip| {
+        // 스트립 높이 + halo를 넘는 행은 메모리에 올라오지 않습니다.
+        assert!(strip.height <= 3 + 2 * 4);
+        strip.gaussian_blur();
+        strip.gaussian_blur();
+      })
+      .unwrap();
+
+    assert_eq!(
+      Image::decode_raw_f32(&output, 23, 17).pixels,
+      expected.pixels
+    );
+  }
+
+  // 동시에 실행되는 테스트끼리 겹치지 않는 임시 디렉터리, 테스트가 실패해도 지웁니다.
+  struct TempDir(std::path::PathBuf);
+
+  impl TempDir {
+    fn new(name: &str) -> Self {
+      let path = std::env::temp_dir().join(format!("tiled_{}_{}", name, std::process::id()));
+      std::fs::create_dir_all(&path).unwrap();
+      Self(path)
+    }
+  }
+
+  impl Drop for TempDir {
+    fn drop(&mut self) {
+      let _ = std::fs::remove_dir_all(&self.0);
+    }
+  }
+
+  #[test]
+  fn test_netpbm_and_png_streams() {
+    let image = Image::test_pattern(19, 11);
+    let ppm = image.encode_ppm(NetpbmEncoding::Binary);
+
+    // PPM -> PPM 항등 변환은 같은 바이트를 만들어야 합니다.
+    let processor = StripProcessor {
+      strip_height: 4,
+      halo: 0,
+    };
+    let mut source = NetpbmRowReader::new(Cursor::new(&ppm)).unwrap();
+    let mut output = Vec::new();
+    let mut sink = NetpbmRowWriter::new(&mut output, 19, 11).unwrap();
+    processor.run(&mut source, &mut sink, |_| {}).unwrap();
+    assert_eq!(output, ppm);
+
+    // 파일 확장자로 형식을 고르는 PPM -> PNG 변환 후 다시 읽기
+    let dir = TempDir::new("netpbm_and_png_streams");
+    let (input, output) = (dir.0.join("input.ppm"), dir.0.join("output.png"));
+    std::fs::write(&input, &ppm).unwrap();
+    processor.run_files(&input, &output, |_| {}).unwrap();
+
+    let quantized = Image::decode_netpbm(&ppm);
+    let mut reader = open_row_source(&output).unwrap();
+    assert_eq!((reader.width(), reader.height()), (19, 11));
+    let mut row = vec![[0.0; 4]; 19];
+    for j in 0..11 {
+      reader.read_row(&mut row).unwrap();
+      for (i, pixel) in row.iter().enumerate() {
+        assert_eq!(pixel, quantized.get_pixel(i as i32, j));
+      }
+    }
+  }
+}
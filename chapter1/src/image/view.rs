@@ -0,0 +1,295 @@
+use super::Image;
+
+// Image의 직사각형 영역을 복사 없이 빌려오는 뷰
+// stride는 원본 이미지의 한 행에 들어있는 픽셀 수입니다.
+#[derive(Clone, Copy)]
+pub struct ImageView<'a> {
+  pixels: &'a [[f32; 4]],
+  width: u32,
+  height: u32,
+  stride: u32,
+}
+
+pub struct ImageViewMut<'a> {
+  pixels: &'a mut [[f32; 4]],
+  width: u32,
+  height: u32,
+  stride: u32,
+}
+
+// 영역의 첫 픽셀부터 마지막 행의 끝 픽셀까지를 포함하는 슬라이스 범위
+fn region_range(stride: u32, x: u32, y: u32, width: u32, height: u32) -> std::ops::Range<usize> {
+  let start = (y * stride + x) as usize;
+  let len = if width == 0 || height == 0 {
+    0
+  } else {
+    ((height - 1) * stride + width) as usize
+  };
+  start..start + len
+}
+
+fn check_region(parent_width: u32, parent_height: u32, x: u32, y: u32, width: u32, height: u32) {
+  assert!(
+    x + width <= parent_width && y + height <= parent_height,
+    "View region ({}, {}, {}x{}) is out of bounds ({}x{})",
+    x,
+    y,
+    width,
+    height,
+    parent_width,
+    parent_height
+  );
+}
+
+impl Image {
+  pub fn as_view(&self) -> ImageView<'_> {
+    self.view(0, 0, self.width, self.height)
+  }
+
+  pub fn as_view_mut(&mut self) -> ImageViewMut<'_> {
+    self.view_mut(0, 0, self.width, self.height)
+  }
+
+  pub fn view(&self, x: u32, y: u32, width: u32, height: u32) -> ImageView<'_> {
+    check_region(self.width, self.height, x, y, width, height);
+
+    ImageView {
+      pixels: &self.pixels[region_range(self.width, x, y, width, height)],
+      width,
+      height,
+      stride: self.width,
+    }
+  }
+
+  pub fn view_mut(&mut self, x: u32, y: u32, width: u32, height: u32) -> ImageViewMut<'_> {
+    check_region(self.width, self.height, x, y, width, height);
+
+    let stride = self.width;
+    ImageViewMut {
+      pixels: &mut self.pixels[region_range(stride, x, y, width, height)],
+      width,
+      height,
+      stride,
+    }
+  }
+}
+
+impl<'a> ImageView<'a> {
+  pub fn width(&self) -> u32 {
+    self.width
+  }
+
+  pub fn height(&self) -> u32 {
+    self.height
+  }
+
+  pub fn stride(&self) -> u32 {
+    self.stride
+  }
+
+  pub fn row(&self, j: u32) -> &'a [[f32; 4]] {
+    let start = (j * self.stride) as usize;
+    &self.pixels[start..start + self.width as usize]
+  }
+
+  // Image::get_pixel과 마찬가지로 범위를 벗어나면 뷰의 가장자리로 고정
+  pub fn get_pixel(&self, i: i32, j: i32) -> &'a [f32; 4] {
+    let i = i.clamp(0, self.width as i32 - 1);
+    let j = j.clamp(0, self.height as i32 - 1);
+
+    &self.pixels[(i + self.stride as i32 * j) as usize]
+  }
+
+  pub fn sub_view(&self, x: u32, y: u32, width: u32, height: u32) -> ImageView<'a> {
+    check_region(self.width, self.height, x, y, width, height);
+
+    ImageView {
+      pixels: &self.pixels[region_range(self.stride, x, y, width, height)],
+      width,
+      height,
+      stride: self.stride,
+    }
+  }
+
+  // 뷰 영역의 평균 색
+  pub fn mean(&self) -> [f32; 4] {
+    let mut sum = [0.0; 4];
+    for j in 0..self.height {
+      for pixel in self.row(j) {
+        for (s, v) in sum.iter_mut().zip(pixel) {
+          *s += v;
+        }
+      }
+    }
+    let count = (self.width * self.height).max(1) as f32;
+    sum.map(|s| s / count)
+  }
+
+  // 뷰 영역만 잘라서 새 이미지로 복사
+  pub fn to_image(self) -> Image {
+    let mut image = Image::new(self.width, self.height);
+    for j in 0..self.height {
+      let start = (j * self.width) as usize;
+      image.pixels[start..start + self.width as usize].copy_from_slice(self.row(j));
+    }
+    image
+  }
+}
+
+impl ImageViewMut<'_> {
+  pub fn width(&self) -> u32 {
+    self.width
+  }
+
+  pub fn height(&self) -> u32 {
+    self.height
+  }
+
+  pub fn stride(&self) -> u32 {
+    self.stride
+  }
+
+  pub fn as_view(&self) -> ImageView<'_> {
+    ImageView {
+      pixels: self.pixels,
+      width: self.width,
+      height: self.height,
+      stride: self.stride,
+    }
+  }
+
+  pub fn row(&self, j: u32) -> &[[f32; 4]] {
+    let start = (j * self.stride) as usize;
+    &self.pixels[start..start + self.width as usize]
+  }
+
+  pub fn row_mut(&mut self, j: u32) -> &mut [[f32; 4]] {
+    let start = (j * self.stride) as usize;
+    &mut self.pixels[start..start + self.width as usize]
+  }
+
+  pub fn get_pixel(&self, i: i32, j: i32) -> &[f32; 4] {
+    let i = i.clamp(0, self.width as i32 - 1);
+    let j = j.clamp(0, self.height as i32 - 1);
+
+    &self.pixels[(i + self.stride as i32 * j) as usize]
+  }
+
+  pub fn get_pixel_mut(&mut self, i: i32, j: i32) -> &mut [f32; 4] {
+    let i = i.clamp(0, self.width as i32 - 1);
+    let j = j.clamp(0, self.height as i32 - 1);
+
+    &mut self.pixels[(i + self.stride as i32 * j) as usize]
+  }
+
+  pub fn sub_view_mut(&mut self, x: u32, y: u32, width: u32, height: u32) -> ImageViewMut<'_> {
+    check_region(self.width, self.height, x, y, width, height);
+
+    ImageViewMut {
+      pixels: &mut self.pixels[region_range(self.stride, x, y, width, height)],
+      width,
+      height,
+      stride: self.stride,
+    }
+  }
+
+  pub fn fill(&mut self, color: [f32; 4]) {
+    for j in 0..self.height {
+      self.row_mut(j).fill(color);
+    }
+  }
+
+  // 같은 크기의 다른 뷰에서 픽셀을 복사 (부분 업데이트, 아틀라스 배치 등)
+  pub fn copy_from(&mut self, src: &ImageView) {
+    assert!(
+      src.width == self.width && src.height == self.height,
+      "View size mismatch: {}x{} vs {}x{}",
+      src.width,
+      src.height,
+      self.width,
+      self.height
+    );
+
+    for j in 0..self.height {
+      self.row_mut(j).copy_from_slice(src.row(j));
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn gradient(width: u32, height: u32) -> Image {
+    let mut image = Image::new(width, height);
+    for j in 0..height as i32 {
+      for i in 0..width as i32 {
+        *image.get_pixel_mut(i, j) = [i as f32, j as f32, 0.0, 1.0];
+      }
+    }
+    image
+  }
+
+  #[test]
+  fn test_view_uses_parent_stride() {
+    let image = gradient(8, 6);
+    let view = image.view(2, 3, 4, 2);
+
+    assert_eq!(view.stride(), 8);
+    assert_eq!(view.get_pixel(0, 0), &[2.0, 3.0, 0.0, 1.0]);
+    assert_eq!(view.get_pixel(3, 1), &[5.0, 4.0, 0.0, 1.0]);
+    // 범위 밖은 뷰의 가장자리로 고정
+    assert_eq!(view.get_pixel(10, -1), &[5.0, 3.0, 0.0, 1.0]);
+    assert_eq!(view.row(1).len(), 4);
+  }
+
+  #[test]
+  fn test_view_mut_only_touches_region() {
+    let mut image = gradient(5, 5);
+    image.view_mut(1, 1, 3, 2).fill([9.0; 4]);
+
+    for j in 0..5 {
+      for i in 0..5 {
+        let inside = (1..4).contains(&i) && (1..3).contains(&j);
+        assert_eq!(image.get_pixel(i, j)[0] == 9.0, inside);
+      }
+    }
+  }
+
+  #[test]
+  fn test_copy_from_and_to_image() {
+    let src = gradient(4, 4);
+    let mut dst = Image::new(6, 6);
+    dst.view_mut(2, 1, 2, 2).copy_from(&src.view(1, 1, 2, 2));
+
+    let copied = dst.view(2, 1, 2, 2).to_image();
+    assert_eq!(copied.pixels, src.view(1, 1, 2, 2).to_image().pixels);
+    assert_eq!(dst.get_pixel(0, 0), &[0.0; 4]);
+  }
+
+  #[test]
+  fn test_nested_views() {
+    let mut image = gradient(8, 6);
+    assert_eq!((image.width(), image.height()), (8, 6));
+
+    let view = image.view(1, 1, 6, 4);
+    let sub = view.sub_view(2, 1, 3, 2);
+    assert_eq!((sub.width(), sub.height(), sub.stride()), (3, 2, 8));
+    assert_eq!(sub.get_pixel(0, 0), &[3.0, 2.0, 0.0, 1.0]);
+
+    let mut view = image.view_mut(1, 1, 6, 4);
+    let mut sub = view.sub_view_mut(2, 1, 3, 2);
+    sub.fill([9.0; 4]);
+    assert_eq!(sub.stride(), 8);
+    assert_eq!(sub.as_view().mean(), [9.0; 4]);
+    assert_eq!(image.get_pixel(3, 2)[0], 9.0);
+    assert_eq!(image.get_pixel(2, 2)[0], 2.0);
+  }
+
+  #[test]
+  #[should_panic]
+  fn test_view_out_of_bounds() {
+    let image = Image::new(4, 4);
+    image.view(2, 2, 3, 1);
+  }
+}
@@ -1,4 +1,5 @@
 mod app;
+#[cfg_attr(not(test), allow(dead_code))]
 mod image;
 mod state;
 
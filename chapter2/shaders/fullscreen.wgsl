@@ -0,0 +1,23 @@
+@group(0) @binding(0)
+var raster: texture_2d<f32>;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+};
+
+// 정점 번호 0, 1, 2로 화면 전체를 덮는 큰 삼각형을 만듭니다.
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    var output: VertexOutput;
+    output.position = vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+    return output;
+}
+
+// 래스터라이저 해상도가 창과 같으므로 픽셀 좌표로 바로 읽습니다.
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let size = vec2<i32>(textureDimensions(raster));
+    let pixel = min(vec2<i32>(in.position.xy), size - 1);
+    return textureLoad(raster, pixel, 0);
+}
@@ -1,4 +1,4 @@
-use glam::{Vec2, Vec3};
+use glam::{Mat4, Vec2, Vec3, Vec4};
 use std::vec::Vec;
 
 #[derive(Clone, Debug)]
@@ -7,6 +7,12 @@ pub struct MyVertex {
   color: Vec3,
 }
 
+impl MyVertex {
+  pub fn new(pos: Vec3, color: Vec3) -> Self {
+    Self { pos, color }
+  }
+}
+
 #[derive(Clone, Debug)]
 pub struct MyTriangle {
   v0: MyVertex,
@@ -14,52 +20,315 @@ pub struct MyTriangle {
   v2: MyVertex,
 }
 
+// 인덱스 버퍼를 삼각형으로 묶는 방식 (wgpu::PrimitiveTopology와 같은 의미)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Topology {
+  // 인덱스 세 개마다 삼각형 하나
+  TriangleList,
+  // 처음 두 인덱스 다음부터 인덱스 하나마다 직전 두 정점과 삼각형 하나
+  TriangleStrip,
+}
+
+#[derive(Clone, Debug)]
+pub struct MyMesh {
+  vertices: Vec<MyVertex>,
+  indices: Vec<u32>,
+  topology: Topology,
+}
+
+impl MyMesh {
+  pub fn new(vertices: Vec<MyVertex>, indices: Vec<u32>, topology: Topology) -> Self {
+    assert!(
+      indices
+        .iter()
+        .all(|&index| (index as usize) < vertices.len()),
+      "Index out of range"
+    );
+
+    Self {
+      vertices,
+      indices,
+      topology,
+    }
+  }
+
+  // 삼각형마다 세 정점의 인덱스
+  // 스트립의 홀수 번째 삼각형은 앞의 두 정점을 바꿔서 모든 삼각형의 감기 방향을 맞춥니다.
+  pub fn triangle_indices(&self) -> Vec<[u32; 3]> {
+    match self.topology {
+      Topology::TriangleList => self
+        .indices
+        .chunks_exact(3)
+        .map(|t| [t[0], t[1], t[2]])
+        .collect(),
+      Topology::TriangleStrip => self
+        .indices
+        .windows(3)
+        .enumerate()
+        .map(|(k, t)| {
+          if k % 2 == 0 {
+            [t[0], t[1], t[2]]
+          } else {
+            [t[1], t[0], t[2]]
+          }
+        })
+        .collect(),
+    }
+  }
+
+  pub fn triangles(&self) -> impl Iterator<Item = MyTriangle> + '_ {
+    self
+      .triangle_indices()
+      .into_iter()
+      .map(|[i0, i1, i2]| MyTriangle {
+        v0: self.vertices[i0 as usize].clone(),
+        v1: self.vertices[i1 as usize].clone(),
+        v2: self.vertices[i2 as usize].clone(),
+      })
+  }
+}
+
+// 깊이 비교 함수, 새 깊이가 저장된 깊이와 비교해서 통과하면 픽셀을 씁니다.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompareFunction {
+  Less,
+  LessEqual,
+  Greater,
+  Always,
+}
+
+impl CompareFunction {
+  pub fn passes(self, depth: f32, stored: f32) -> bool {
+    match self {
+      CompareFunction::Less => depth < stored,
+      CompareFunction::LessEqual => depth <= stored,
+      CompareFunction::Greater => depth > stored,
+      CompareFunction::Always => true,
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DepthState {
+  pub compare: CompareFunction,
+  // false이면 깊이 테스트만 하고 깊이 버퍼는 그대로 둡니다. (반투명 물체 등)
+  pub write_enabled: bool,
+  // 매 프레임 깊이 버퍼를 채우는 값
+  pub clear_value: f32,
+}
+
+impl Default for DepthState {
+  fn default() -> Self {
+    Self {
+      compare: CompareFunction::Less,
+      write_enabled: true,
+      clear_value: 1.0,
+    }
+  }
+}
+
+// 투영 방식, 깊이는 wgpu와 같이 가까운 평면이 0, 먼 평면이 1입니다.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Projection {
+  // fov_y는 세로 시야각 (라디안)
+  Perspective { fov_y: f32, near: f32, far: f32 },
+  // height는 화면 세로에 들어가는 월드 공간 길이
+  Orthographic { height: f32, near: f32, far: f32 },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Camera {
+  pub eye: Vec3,
+  pub target: Vec3,
+  pub up: Vec3,
+  pub projection: Projection,
+}
+
+impl Default for Camera {
+  // z = 1에서 원점을 바라보고 세로로 [-1, 1]이 보이는 정사영 카메라
+  fn default() -> Self {
+    Self {
+      eye: Vec3::new(0.0, 0.0, 1.0),
+      target: Vec3::ZERO,
+      up: Vec3::Y,
+      projection: Projection::Orthographic {
+        height: 2.0,
+        near: 0.0,
+        far: 2.0,
+      },
+    }
+  }
+}
+
+impl Camera {
+  pub fn view(&self) -> Mat4 {
+    Mat4::look_at_rh(self.eye, self.target, self.up)
+  }
+
+  pub fn projection(&self, aspect: f32) -> Mat4 {
+    match self.projection {
+      Projection::Perspective { fov_y, near, far } => {
+        Mat4::perspective_rh(fov_y, aspect, near, far)
+      }
+      Projection::Orthographic { height, near, far } => {
+        let (half_width, half_height) = (height * aspect * 0.5, height * 0.5);
+        Mat4::orthographic_rh(
+          -half_width,
+          half_width,
+          -half_height,
+          half_height,
+          near,
+          far,
+        )
+      }
+    }
+  }
+
+  pub fn view_projection(&self, aspect: f32) -> Mat4 {
+    self.projection(aspect) * self.view()
+  }
+}
+
+// 클립 공간의 정점과 보간할 속성들 (varyings)
+#[derive(Clone, Copy, Debug)]
+struct ClipVertex {
+  clip: Vec4,
+  color: Vec3,
+}
+
+impl ClipVertex {
+  fn lerp(&self, other: &ClipVertex, t: f32) -> ClipVertex {
+    ClipVertex {
+      clip: self.clip.lerp(other.clip, t),
+      color: self.color.lerp(other.color, t),
+    }
+  }
+}
+
+// 가까운 평면(z >= 0) 뒤로 넘어간 부분을 잘라낸 볼록 다각형 (Sutherland-Hodgman)
+// w가 0 이하인 정점을 나누기 전에 없애야 투영이 뒤집히지 않습니다.
+fn clip_near(vertices: [ClipVertex; 3]) -> Vec<ClipVertex> {
+  let mut output = Vec::with_capacity(4);
+  for k in 0..3 {
+    let (a, b) = (vertices[k], vertices[(k + 1) % 3]);
+    let (da, db) = (a.clip.z, b.clip.z);
+    if da >= 0.0 {
+      output.push(a);
+    }
+    if (da >= 0.0) != (db >= 0.0) {
+      output.push(a.lerp(&b, da / (da - db)));
+    }
+  }
+  output
+}
+
+// 래스터 좌표를 1/256 픽셀 단위의 정수로 맞춥니다. (D3D의 8비트 서브픽셀 정밀도)
+const SUBPIXEL_STEPS: i64 = 256;
+
+// 정수 좌표의 에지 함수, 곱이 커질 수 있으므로 i128로 계산합니다.
+fn edge_fixed(v0: [i64; 2], v1: [i64; 2], point: [i64; 2]) -> i128 {
+  let [dx, dy] = [0, 1].map(|c| i128::from(v1[c] - v0[c]));
+  let [px, py] = [0, 1].map(|c| i128::from(point[c] - v0[c]));
+  px * dy - py * dx
+}
+
+// 그리는 감기 방향에서 내부가 오른쪽(y가 아래)인 변 중 위쪽 변은 수평으로 왼쪽을 향하고,
+// 왼쪽 변은 아래로 향합니다.
+fn is_top_left(v0: [i64; 2], v1: [i64; 2]) -> bool {
+  (v0[1] == v1[1] && v1[0] < v0[0]) || v1[1] > v0[1]
+}
+
 pub struct Rasterization {
   width: i32,
   height: i32,
-  triangle: MyTriangle,
+  meshes: Vec<MyMesh>,
+  depth_state: DepthState,
+  camera: Camera,
 }
 
 impl Rasterization {
   pub fn new(width: i32, height: i32) -> Self {
-    let triangle = MyTriangle {
-      v0: MyVertex {
-        pos: Vec3::new(0.0, 0.0, 0.0),
-        color: Vec3::new(1.0, 0.0, 0.0),
-      },
-      v1: MyVertex {
-        pos: Vec3::new(1.0, 0.0, 0.0),
-        color: Vec3::new(0.0, 1.0, 0.0),
-      },
-      v2: MyVertex {
-        pos: Vec3::new(0.0, 1.0, 0.0),
-        color: Vec3::new(0.0, 0.0, 1.0),
-      },
-    };
+    let triangle = MyMesh::new(
+      vec![
+        MyVertex::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0)),
+        MyVertex::new(Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)),
+        MyVertex::new(Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 0.0, 1.0)),
+      ],
+      vec![0, 1, 2],
+      Topology::TriangleList,
+    );
 
+    // 스트립으로 그리는 사각형, 삼각형보다 나중에 그리지만 뒤에 있어서 일부가 가려집니다.
+    let quad = MyMesh::new(
+      vec![
+        MyVertex::new(Vec3::new(-0.5, -0.5, -0.5), Vec3::new(1.0, 1.0, 0.0)),
+        MyVertex::new(Vec3::new(0.3, -0.5, -0.5), Vec3::new(0.0, 1.0, 1.0)),
+        MyVertex::new(Vec3::new(-0.5, 0.3, -0.5), Vec3::new(1.0, 0.0, 1.0)),
+        MyVertex::new(Vec3::new(0.3, 0.3, -0.5), Vec3::new(1.0, 1.0, 1.0)),
+      ],
+      vec![0, 1, 2, 3],
+      Topology::TriangleStrip,
+    );
+
+    Self::with_meshes(width, height, vec![triangle, quad])
+  }
+
+  pub fn with_meshes(width: i32, height: i32, meshes: Vec<MyMesh>) -> Self {
     Self {
       width,
       height,
-      triangle,
+      meshes,
+      depth_state: DepthState::default(),
+      camera: Camera::default(),
     }
   }
 
-  pub fn project_world_to_raster(&self, point: Vec3) -> Vec2 {
+  pub fn camera(&self) -> &Camera {
+    &self.camera
+  }
+
+  pub fn camera_mut(&mut self) -> &mut Camera {
+    &mut self.camera
+  }
+
+  pub fn depth_state_mut(&mut self) -> &mut DepthState {
+    &mut self.depth_state
+  }
+
+  pub fn width(&self) -> i32 {
+    self.width
+  }
+
+  pub fn height(&self) -> i32 {
+    self.height
+  }
+
+  // 창 크기가 바뀌면 장면은 그대로 두고 출력 해상도만 바꿉니다.
+  pub fn resize(&mut self, width: i32, height: i32) {
+    self.width = width;
+    self.height = height;
+  }
+
+  fn view_projection(&self) -> Mat4 {
     // 종횡비(aspect ratio) 계산
     let aspect = self.width as f32 / self.height as f32;
+    self.camera.view_projection(aspect)
+  }
 
-    // 종횡비를 고려하여 NDC 좌표계로 변환
-    let point_ndc = Vec2::new(point.x / aspect, point.y);
-
+  // NDC 좌표를 래스터 좌표로 변환 (y축 방향을 뒤집습니다)
+  // 픽셀 (i, j)는 [i, i + 1) x [j, j + 1)을 덮고, 중심 (i + 0.5, j + 0.5)에서 샘플링합니다.
+  fn ndc_to_raster(&self, ndc: Vec2) -> Vec2 {
     // 래스터 변환을 위한 스케일 계수
     let x_scale = 2.0 / self.width as f32;
     let y_scale = 2.0 / self.height as f32;
 
-    // y축 방향을 뒤집어서 래스터 좌표계로 변환
-    Vec2::new(
-      (point_ndc.x + 1.0) / x_scale - 0.5,
-      (1.0 - point_ndc.y) / y_scale - 0.5,
-    )
+    Vec2::new((ndc.x + 1.0) / x_scale, (1.0 - ndc.y) / y_scale)
+  }
+
+  pub fn project_world_to_raster(&self, point: Vec3) -> Vec2 {
+    // 클립 공간으로 변환한 뒤 동차 나눗셈으로 NDC 좌표를 구합니다.
+    let clip = self.view_projection() * point.extend(1.0);
+    self.ndc_to_raster(clip.truncate().truncate() / clip.w)
   }
 
   pub fn edge_function(&self, v0: Vec2, v1: Vec2, point: Vec2) -> f32 {
@@ -67,52 +336,104 @@ impl Rasterization {
   }
 
   pub fn render(&self) -> Vec<[f32; 4]> {
-    let mut pixels = vec![[0.0; 4]; (self.width * self.height) as usize];
+    self.render_with_depth().0
+  }
+
+  // 색 버퍼와 깊이 버퍼 (깊이는 정점 z를 중심 좌표로 보간한 값)
+  pub fn render_with_depth(&self) -> (Vec<[f32; 4]>, Vec<f32>) {
+    let count = (self.width * self.height) as usize;
+    let mut pixels = vec![[0.0; 4]; count];
+    let mut depth = vec![self.depth_state.clear_value; count];
+
+    let view_projection = self.view_projection();
+    for mesh in self.meshes.iter() {
+      for triangle in mesh.triangles() {
+        let vertices = [&triangle.v0, &triangle.v1, &triangle.v2].map(|v| ClipVertex {
+          clip: view_projection * v.pos.extend(1.0),
+          color: v.color,
+        });
+
+        // 잘라낸 다각형을 부채꼴로 다시 삼각형으로 나눕니다.
+        let polygon = clip_near(vertices);
+        for k in 1..polygon.len().saturating_sub(1) {
+          let triangle = [polygon[0], polygon[k], polygon[k + 1]];
+          self.draw_triangle(&triangle, &mut pixels, &mut depth);
+        }
+      }
+    }
+
+    (pixels, depth)
+  }
+
+  fn draw_triangle(&self, triangle: &[ClipVertex; 3], pixels: &mut [[f32; 4]], depth: &mut [f32]) {
+    // 동차 나눗셈, 1/w는 원근 보정 보간에 사용합니다.
+    let inv_w = triangle.map(|v| 1.0 / v.clip.w);
+    let z = [0, 1, 2].map(|k| triangle[k].clip.z * inv_w[k]);
 
-    // 정점들을 래스터 공간으로 투영
-    let v0 = self.project_world_to_raster(self.triangle.v0.pos);
-    let v1 = self.project_world_to_raster(self.triangle.v1.pos);
-    let v2 = self.project_world_to_raster(self.triangle.v2.pos);
+    // 정점을 서브픽셀 격자에 맞춥니다. 덮임 판정을 정수로 하므로 공유하는 변에서 두 삼각형의 결과가 정확히 반대입니다.
+    let fixed = [0, 1, 2].map(|k| {
+      let raster = self.ndc_to_raster(triangle[k].clip.truncate().truncate() * inv_w[k]);
+      [raster.x, raster.y].map(|c| (c * SUBPIXEL_STEPS as f32).round() as i64)
+    });
+    let [v0, v1, v2] = fixed.map(|[x, y]| Vec2::new(x as f32, y as f32) / SUBPIXEL_STEPS as f32);
 
-    // 경계 상자(bounding box) 찾기
-    let x_min = v0.x.min(v1.x).min(v2.x).max(0.0) as i32;
-    let x_max = v0.x.max(v1.x).max(v2.x).min(self.width as f32 - 1.0) as i32;
-    let y_min = v0.y.min(v1.y).min(v2.y).max(0.0) as i32;
-    let y_max = v0.y.max(v1.y).max(v2.y).min(self.height as f32 - 1.0) as i32;
+    // 넓이가 0이거나 뒤집힌 삼각형은 그리지 않습니다.
+    if edge_fixed(fixed[0], fixed[1], fixed[2]) <= 0 {
+      return;
+    }
+    // 공유하는 변 위의 픽셀 중심은 위쪽 또는 왼쪽 변을 가진 삼각형만 그립니다.
+    let bias = [(1, 2), (2, 0), (0, 1)].map(|(a, b)| i128::from(!is_top_left(fixed[a], fixed[b])));
+
+    // 경계 상자(bounding box) 찾기, 중심이 상자 안에 있는 픽셀만 살펴봅니다.
+    let x_min = ((v0.x.min(v1.x).min(v2.x) - 0.5).ceil().max(0.0)) as i32;
+    let x_max = ((v0.x.max(v1.x).max(v2.x) - 0.5).floor()).min(self.width as f32 - 1.0) as i32;
+    let y_min = ((v0.y.min(v1.y).min(v2.y) - 0.5).ceil().max(0.0)) as i32;
+    let y_max = ((v0.y.max(v1.y).max(v2.y) - 0.5).floor()).min(self.height as f32 - 1.0) as i32;
 
     // 경계 상자 내의 각 픽셀에 대해 반복
     for j in y_min..=y_max {
       for i in x_min..=x_max {
-        let point = Vec2::new(i as f32, j as f32);
+        let center = [i, j].map(|c| c as i64 * SUBPIXEL_STEPS + SUBPIXEL_STEPS / 2);
+
+        // 위쪽/왼쪽 변이 아니면 변 위의 점(값이 0)을 밖으로 봅니다.
+        let inside = [(1, 2), (2, 0), (0, 1)]
+          .iter()
+          .zip(bias)
+          .all(|(&(a, b), bias)| edge_fixed(fixed[a], fixed[b], center) >= bias);
+        if !inside {
+          continue;
+        }
 
-        // 중심 좌표(barycentric coordinates) 계산
+        // 중심 좌표(barycentric coordinates) 계산 및 정규화
+        let point = Vec2::new(i as f32 + 0.5, j as f32 + 0.5);
         let alpha0 = self.edge_function(v1, v2, point);
         let alpha1 = self.edge_function(v2, v0, point);
         let alpha2 = self.edge_function(v0, v1, point);
+        let total = alpha0 + alpha1 + alpha2;
+        let (alpha0, alpha1, alpha2) = (alpha0 / total, alpha1 / total, alpha2 / total);
 
-        // point가 삼각형 내부에 있을 때, 다시 말해 alpha0, alpha1, alpha2가 모두 양수인지 확인
-        if alpha0 >= 0.0 && alpha1 >= 0.0 && alpha2 >= 0.0 {
-          // 중심 좌표 정규화
-          let total = alpha0 + alpha1 + alpha2;
+        // NDC 깊이(z/w)는 화면 공간에서 선형이므로 그대로 보간합니다.
+        // 먼 평면 밖의 픽셀은 버립니다.
+        let idx = (i + j * self.width) as usize;
+        let z = z[0] * alpha0 + z[1] * alpha1 + z[2] * alpha2;
+        if !(0.0..=1.0).contains(&z) || !self.depth_state.compare.passes(z, depth[idx]) {
+          continue;
+        }
+        if self.depth_state.write_enabled {
+          depth[idx] = z;
+        }
 
-          let (alpha0, alpha1, alpha2) = if total != 0.0 {
-            (alpha0 / total, alpha1 / total, alpha2 / total)
-          } else {
-            (1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0)
-          };
+        // 원근 보정: 속성/w와 1/w를 화면 공간에서 보간한 뒤 나눕니다.
+        let weights = [alpha0 * inv_w[0], alpha1 * inv_w[1], alpha2 * inv_w[2]];
+        let inv_total = 1.0 / (weights[0] + weights[1] + weights[2]);
+        let [b0, b1, b2] = weights.map(|w| w * inv_total);
 
-          // 색상 보간
-          let color = self.triangle.v0.color * alpha0
-            + self.triangle.v1.color * alpha1
-            + self.triangle.v2.color * alpha2;
+        // 색상 보간
+        let color = triangle[0].color * b0 + triangle[1].color * b1 + triangle[2].color * b2;
 
-          let idx = (i + j * self.width) as usize;
-          pixels[idx] = [color.x, color.y, color.z, 1.0];
-        }
+        pixels[idx] = [color.x, color.y, color.z, 1.0];
       }
     }
-
-    pixels
   }
 
   pub fn update(&mut self) {
@@ -131,6 +452,169 @@ mod tests {
     assert_eq!(raster.height, 600);
   }
 
+  #[test]
+  fn test_resize() {
+    let mut raster = Rasterization::new(800, 600);
+    raster.resize(64, 32);
+    assert_eq!((raster.width(), raster.height()), (64, 32));
+    assert_eq!(raster.render().len(), 64 * 32);
+  }
+
+  fn quad(topology: Topology) -> MyMesh {
+    let corners = match topology {
+      Topology::TriangleList => [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)],
+      Topology::TriangleStrip => [(-1.0, -1.0), (1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)],
+    };
+    let vertices = corners
+      .iter()
+      .map(|&(x, y)| MyVertex::new(Vec3::new(x, y, 0.0), Vec3::new(1.0, 1.0, 1.0)))
+      .collect();
+    let indices = match topology {
+      Topology::TriangleList => vec![0, 1, 2, 0, 2, 3],
+      Topology::TriangleStrip => vec![0, 1, 2, 3],
+    };
+    MyMesh::new(vertices, indices, topology)
+  }
+
+  #[test]
+  fn test_mesh_topology() {
+    assert_eq!(
+      quad(Topology::TriangleList).triangle_indices(),
+      vec![[0, 1, 2], [0, 2, 3]]
+    );
+    // 스트립의 두 번째 삼각형은 감기 방향을 유지하도록 순서를 바꿉니다.
+    assert_eq!(
+      quad(Topology::TriangleStrip).triangle_indices(),
+      vec![[0, 1, 2], [2, 1, 3]]
+    );
+  }
+
+  #[test]
+  fn test_render_meshes() {
+    let list = Rasterization::with_meshes(8, 8, vec![quad(Topology::TriangleList)]).render();
+    assert!(list.iter().all(|p| *p == [1.0; 4]));
+
+    let strip = Rasterization::with_meshes(8, 8, vec![quad(Topology::TriangleStrip)]).render();
+    assert_eq!(strip, list);
+  }
+
+  fn flat_quad(z: f32, color: Vec3) -> MyMesh {
+    let corners = [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)];
+    let vertices = corners
+      .iter()
+      .map(|&(x, y)| MyVertex::new(Vec3::new(x, y, z), color))
+      .collect();
+    MyMesh::new(vertices, vec![0, 1, 2, 0, 2, 3], Topology::TriangleList)
+  }
+
+  #[test]
+  fn test_depth_compare_functions() {
+    let red = Vec3::new(1.0, 0.0, 0.0);
+    let green = Vec3::new(0.0, 1.0, 0.0);
+    // 가까운 빨강을 먼저, 먼 초록을 나중에 그립니다.
+    // 기본 카메라에서 z = 0.6은 깊이 0.2, z = -0.2는 깊이 0.6입니다.
+    let mut raster =
+      Rasterization::with_meshes(4, 4, vec![flat_quad(0.6, red), flat_quad(-0.2, green)]);
+
+    let (pixels, depth) = raster.render_with_depth();
+    assert!(pixels.iter().all(|p| *p == [1.0, 0.0, 0.0, 1.0]));
+    assert!(depth.iter().all(|&d| (d - 0.2).abs() < 1e-6));
+
+    raster.depth_state_mut().compare = CompareFunction::Greater;
+    raster.depth_state_mut().clear_value = 0.0;
+    let (pixels, depth) = raster.render_with_depth();
+    assert!(pixels.iter().all(|p| *p == [0.0, 1.0, 0.0, 1.0]));
+    assert!(depth.iter().all(|&d| (d - 0.6).abs() < 1e-6));
+
+    // 깊이 쓰기를 끄면 모두 지우기 값과만 비교하므로 나중에 그린 것이 남습니다.
+    *raster.depth_state_mut() = DepthState {
+      write_enabled: false,
+      ..Default::default()
+    };
+    let (pixels, depth) = raster.render_with_depth();
+    assert!(pixels.iter().all(|p| *p == [0.0, 1.0, 0.0, 1.0]));
+    assert!(depth.iter().all(|&d| d == 1.0));
+
+    raster.depth_state_mut().compare = CompareFunction::Always;
+    raster.depth_state_mut().clear_value = 0.0;
+    assert!(raster.render().iter().all(|p| *p == [0.0, 1.0, 0.0, 1.0]));
+  }
+
+  #[test]
+  fn test_depth_less_equal_ties() {
+    let red = Vec3::new(1.0, 0.0, 0.0);
+    let green = Vec3::new(0.0, 1.0, 0.0);
+    let mut raster =
+      Rasterization::with_meshes(4, 4, vec![flat_quad(0.5, red), flat_quad(0.5, green)]);
+
+    assert!(raster.render().iter().all(|p| *p == [1.0, 0.0, 0.0, 1.0]));
+    raster.depth_state_mut().compare = CompareFunction::LessEqual;
+    assert!(raster.render().iter().all(|p| *p == [0.0, 1.0, 0.0, 1.0]));
+  }
+
+  #[test]
+  fn test_perspective_correct_interpolation() {
+    // 카메라 아래로 멀어지는 바닥, 색은 월드 공간에서 가까운 쪽 0부터 먼 쪽 1까지 변합니다.
+    let (near, far) = (Vec3::ZERO, Vec3::ONE);
+    let floor = MyMesh::new(
+      vec![
+        MyVertex::new(Vec3::new(-20.0, -1.0, -1.0), near),
+        MyVertex::new(Vec3::new(20.0, -1.0, -1.0), near),
+        MyVertex::new(Vec3::new(20.0, -1.0, -9.0), far),
+        MyVertex::new(Vec3::new(-20.0, -1.0, -9.0), far),
+      ],
+      vec![0, 1, 2, 0, 2, 3],
+      Topology::TriangleList,
+    );
+    let mut raster = Rasterization::with_meshes(64, 64, vec![floor]);
+    *raster.camera_mut() = Camera {
+      eye: Vec3::ZERO,
+      target: Vec3::new(0.0, 0.0, -1.0),
+      up: Vec3::Y,
+      projection: Projection::Perspective {
+        fov_y: std::f32::consts::FRAC_PI_2,
+        near: 0.5,
+        far: 20.0,
+      },
+    };
+
+    // 월드 공간의 가운데(z = -5)는 색도 가운데여야 합니다. (화면 공간 선형 보간이면 약 0.9)
+    let point = raster.project_world_to_raster(Vec3::new(0.0, -1.0, -5.0));
+    let pixels = raster.render();
+    let pixel = pixels[(point.x.floor() + point.y.floor() * 64.0) as usize];
+    assert!((pixel[0] - 0.5).abs() < 0.05, "{:?}", pixel);
+  }
+
+  #[test]
+  fn test_near_plane_clipping() {
+    // 카메라 뒤까지 이어진 삼각형은 가까운 평면에서 잘린 부분만 그려집니다.
+    let triangle = MyMesh::new(
+      vec![
+        MyVertex::new(Vec3::new(-1.0, -1.0, 5.0), Vec3::ONE),
+        MyVertex::new(Vec3::new(1.0, -1.0, -5.0), Vec3::ONE),
+        MyVertex::new(Vec3::new(-1.0, 1.0, -5.0), Vec3::ONE),
+      ],
+      vec![0, 1, 2],
+      Topology::TriangleList,
+    );
+    let mut raster = Rasterization::with_meshes(16, 16, vec![triangle]);
+    raster.camera_mut().projection = Projection::Perspective {
+      fov_y: std::f32::consts::FRAC_PI_2,
+      near: 0.1,
+      far: 10.0,
+    };
+
+    let (pixels, depth) = raster.render_with_depth();
+    assert!(pixels.contains(&[1.0; 4]));
+    assert!(depth.iter().all(|d| (0.0..=1.0).contains(d)));
+  }
+
+  #[test]
+  #[should_panic(expected = "Index out of range")]
+  fn test_mesh_index_out_of_range() {
+    MyMesh::new(Vec::new(), vec![0, 1, 2], Topology::TriangleList);
+  }
+
   #[test]
   fn test_project_world_to_raster() {
     let raster = Rasterization::new(800, 600);
@@ -139,13 +623,62 @@ mod tests {
 
     // 예상되는 래스터 좌표 계산
     let aspect = 800.0 / 600.0;
-    let expected_x = ((0.5 / aspect + 1.0) / (2.0 / 800.0)) - 0.5;
-    let expected_y = ((1.0 - 0.5) / (2.0 / 600.0)) - 0.5;
+    let expected_x = (0.5 / aspect + 1.0) / (2.0 / 800.0);
+    let expected_y = (1.0 - 0.5) / (2.0 / 600.0);
 
     assert_eq!(raster_point.x, expected_x);
     assert_eq!(raster_point.y, expected_y);
   }
 
+  // 삼각형을 하나씩 8x8에 그려서 각 픽셀이 몇 번 칠해졌는지 셉니다.
+  fn coverage(triangles: &[[(f32, f32); 3]]) -> Vec<u32> {
+    let mut counts = vec![0; 64];
+    for triangle in triangles {
+      let vertices = triangle
+        .iter()
+        .map(|&(x, y)| MyVertex::new(Vec3::new(x, y, 0.0), Vec3::ONE))
+        .collect();
+      let mesh = MyMesh::new(vertices, vec![0, 1, 2], Topology::TriangleList);
+      let pixels = Rasterization::with_meshes(8, 8, vec![mesh]).render();
+      for (count, pixel) in counts.iter_mut().zip(pixels) {
+        *count += u32::from(pixel[3] == 1.0);
+      }
+    }
+    counts
+  }
+
+  #[test]
+  fn test_shared_edges_are_drawn_once() {
+    // 대각선이 픽셀 중심 8개를 정확히 지나갑니다.
+    let (a, b, c, d) = ((-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0));
+    assert!(coverage(&[[a, b, c], [a, c, d]]).iter().all(|&n| n == 1));
+
+    // 픽셀 중심 (4.5, 4.5)에 놓인 정점을 둘러싼 부채꼴, 수평/수직 변도 중심을 지나갑니다.
+    let center = (0.125, -0.125);
+    let ring = [
+      (1.0, -1.0),
+      (1.0, -0.125),
+      (1.0, 1.0),
+      (0.125, 1.0),
+      (-1.0, 1.0),
+      (-1.0, -0.125),
+      (-1.0, -1.0),
+      (0.125, -1.0),
+    ];
+    let fan: Vec<_> = (0..ring.len())
+      .map(|k| [center, ring[k], ring[(k + 1) % ring.len()]])
+      .collect();
+    assert!(coverage(&fan).iter().all(|&n| n == 1));
+  }
+
+  #[test]
+  fn test_degenerate_triangles_are_skipped() {
+    // 픽셀 중심을 지나는 선분이나 점으로 줄어든 삼각형은 아무것도 그리지 않습니다.
+    let line = [(-1.0, -1.0), (0.0, 0.0), (1.0, 1.0)];
+    let point = [(0.125, -0.125); 3];
+    assert!(coverage(&[line, point]).iter().all(|&n| n == 0));
+  }
+
   #[test]
   fn test_edge_function() {
     let raster = Rasterization::new(800, 600);
@@ -1,14 +1,9 @@
+use crate::rasterization::{CompareFunction, Projection, Rasterization};
 use egui_wgpu::ScreenDescriptor;
-use wgpu::util::DeviceExt;
 use winit::window::Window;
 
-// Vertex 데이터 구조체
-#[repr(C)]
-#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
-struct Vertex {
-  position: [f32; 4],
-  uv: [f32; 2],
-}
+// 래스터라이저의 f32 RGBA 출력을 변환 없이 그대로 올립니다.
+const RASTER_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba32Float;
 
 pub struct State<'window> {
   pub surface: wgpu::Surface<'window>,
@@ -21,7 +16,10 @@ pub struct State<'window> {
   pub egui_state: egui_winit::State,
   pub egui_ctx: egui::Context,
   start_time: std::time::Instant,
-  vertex_buffer: wgpu::Buffer,
+  rasterization: Rasterization,
+  raster_bind_group_layout: wgpu::BindGroupLayout,
+  raster_texture: wgpu::Texture,
+  raster_bind_group: wgpu::BindGroup,
 }
 
 impl<'window> State<'window> {
@@ -31,9 +29,12 @@ impl<'window> State<'window> {
     let (_instance, surface, adapter) = Self::initialize_wgpu(window).await;
     let (device, queue) = Self::create_device_queue(&adapter).await;
     let config = Self::configure_surface(&surface, &adapter, &device, size);
-    let render_pipeline = Self::create_render_pipeline(&device, &config);
+    let raster_bind_group_layout = Self::create_raster_bind_group_layout(&device);
+    let render_pipeline = Self::create_render_pipeline(&device, &config, &raster_bind_group_layout);
     let (egui_ctx, egui_state, egui_renderer) = Self::initialize_egui(window, &device, &config);
-    let vertex_buffer = Self::create_vertex_buffer(&device);
+    let rasterization = Rasterization::new(size.width.max(1) as i32, size.height.max(1) as i32);
+    let (raster_texture, raster_bind_group) =
+      Self::create_raster_texture(&device, &raster_bind_group_layout, size);
 
     Self {
       surface,
@@ -46,7 +47,10 @@ impl<'window> State<'window> {
       egui_state,
       egui_ctx,
       start_time: std::time::Instant::now(),
-      vertex_buffer,
+      rasterization,
+      raster_bind_group_layout,
+      raster_texture,
+      raster_bind_group,
     }
   }
 
@@ -56,6 +60,13 @@ impl<'window> State<'window> {
       self.config.width = new_size.width;
       self.config.height = new_size.height;
       self.surface.configure(&self.device, &self.config);
+
+      // 래스터라이저의 해상도를 창과 같게 유지해서 픽셀이 1:1로 보이게 합니다.
+      self
+        .rasterization
+        .resize(new_size.width as i32, new_size.height as i32);
+      (self.raster_texture, self.raster_bind_group) =
+        Self::create_raster_texture(&self.device, &self.raster_bind_group_layout, new_size);
     }
   }
 
@@ -65,6 +76,9 @@ impl<'window> State<'window> {
       .texture
       .create_view(&wgpu::TextureViewDescriptor::default());
 
+    self.rasterization.update();
+    self.upload_raster();
+
     let full_output = self.update_egui(window);
     let (clipped_meshes, screen_descriptor) = self.prepare_egui_meshes(window, full_output);
     let command_buffer = self.render_frame(&view, &clipped_meshes, &screen_descriptor);
@@ -128,36 +142,97 @@ impl<'window> State<'window> {
     config
   }
 
+  fn create_raster_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+    device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+      label: Some("Raster Bind Group Layout"),
+      entries: &[wgpu::BindGroupLayoutEntry {
+        binding: 0,
+        visibility: wgpu::ShaderStages::FRAGMENT,
+        // Rgba32Float는 필터링할 수 없으므로 샘플러 없이 textureLoad로 읽습니다.
+        ty: wgpu::BindingType::Texture {
+          sample_type: wgpu::TextureSampleType::Float { filterable: false },
+          view_dimension: wgpu::TextureViewDimension::D2,
+          multisampled: false,
+        },
+        count: None,
+      }],
+    })
+  }
+
+  fn create_raster_texture(
+    device: &wgpu::Device, layout: &wgpu::BindGroupLayout, size: winit::dpi::PhysicalSize<u32>,
+  ) -> (wgpu::Texture, wgpu::BindGroup) {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+      label: Some("Raster Texture"),
+      size: wgpu::Extent3d {
+        width: size.width.max(1),
+        height: size.height.max(1),
+        depth_or_array_layers: 1,
+      },
+      mip_level_count: 1,
+      sample_count: 1,
+      dimension: wgpu::TextureDimension::D2,
+      format: RASTER_FORMAT,
+      usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+      view_formats: &[],
+    });
+
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+      label: Some("Raster Bind Group"),
+      layout,
+      entries: &[wgpu::BindGroupEntry {
+        binding: 0,
+        resource: wgpu::BindingResource::TextureView(&view),
+      }],
+    });
+    (texture, bind_group)
+  }
+
+  // 매 프레임 CPU 래스터라이저의 결과를 텍스처로 복사합니다.
+  fn upload_raster(&mut self) {
+    let pixels = self.rasterization.render();
+    let size = self.raster_texture.size();
+    self.queue.write_texture(
+      wgpu::TexelCopyTextureInfo {
+        texture: &self.raster_texture,
+        mip_level: 0,
+        origin: wgpu::Origin3d::ZERO,
+        aspect: wgpu::TextureAspect::All,
+      },
+      bytemuck::cast_slice(&pixels),
+      wgpu::TexelCopyBufferLayout {
+        offset: 0,
+        bytes_per_row: Some(size.width * std::mem::size_of::<[f32; 4]>() as u32),
+        rows_per_image: Some(size.height),
+      },
+      size,
+    );
+  }
+
   fn create_render_pipeline(
     device: &wgpu::Device, config: &wgpu::SurfaceConfiguration,
+    raster_bind_group_layout: &wgpu::BindGroupLayout,
   ) -> wgpu::RenderPipeline {
     let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
       label: Some("Shader"),
-      source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/vertex_shader.wgsl").into()),
+      source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/fullscreen.wgsl").into()),
+    });
+
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+      label: Some("Render Pipeline Layout"),
+      bind_group_layouts: &[raster_bind_group_layout],
+      push_constant_ranges: &[],
     });
 
     device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
       label: Some("Render Pipeline"),
-      layout: None,
+      layout: Some(&layout),
       vertex: wgpu::VertexState {
         module: &shader,
         entry_point: Some("vs_main"),
-        buffers: &[wgpu::VertexBufferLayout {
-          array_stride: std::mem::size_of::<[f32; 6]>() as u64,
-          step_mode: wgpu::VertexStepMode::Vertex,
-          attributes: &[
-            wgpu::VertexAttribute {
-              format: wgpu::VertexFormat::Float32x4,
-              offset: 0,
-              shader_location: 0,
-            },
-            wgpu::VertexAttribute {
-              format: wgpu::VertexFormat::Float32x2,
-              offset: 16,
-              shader_location: 1,
-            },
-          ],
-        }],
+        // 정점 버퍼 없이 정점 번호로 화면을 덮는 삼각형을 만듭니다.
+        buffers: &[],
         compilation_options: wgpu::PipelineCompilationOptions::default(),
       },
       fragment: Some(wgpu::FragmentState {
@@ -192,33 +267,55 @@ impl<'window> State<'window> {
     (egui_ctx, egui_state, egui_renderer)
   }
 
-  fn create_vertex_buffer(device: &wgpu::Device) -> wgpu::Buffer {
-    device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-      label: Some("Vertex Buffer"),
-      contents: bytemuck::cast_slice(&[
-        Vertex {
-          position: [-0.5, -0.5, 0.0, 1.0],
-          uv: [0.0, 0.0],
-        },
-        Vertex {
-          position: [0.5, -0.5, 0.0, 1.0],
-          uv: [1.0, 0.0],
-        },
-        Vertex {
-          position: [0.0, 0.5, 0.0, 1.0],
-          uv: [0.5, 1.0],
-        },
-      ]),
-      usage: wgpu::BufferUsages::VERTEX,
-    })
-  }
-
   fn update_egui(&mut self, window: &Window) -> egui::FullOutput {
     self
       .egui_ctx
       .run(self.egui_state.take_egui_input(window), |ctx| {
         egui::Window::new("Controls").show(ctx, |ui| {
           ui.label("Hello from egui!");
+          ui.label(format!(
+            "Raster: {}x{}",
+            self.rasterization.width(),
+            self.rasterization.height()
+          ));
+
+          let depth = self.rasterization.depth_state_mut();
+          egui::ComboBox::from_label("Depth compare")
+            .selected_text(format!("{:?}", depth.compare))
+            .show_ui(ui, |ui| {
+              for compare in [
+                CompareFunction::Less,
+                CompareFunction::LessEqual,
+                CompareFunction::Greater,
+                CompareFunction::Always,
+              ] {
+                ui.selectable_value(&mut depth.compare, compare, format!("{:?}", compare));
+              }
+            });
+          ui.checkbox(&mut depth.write_enabled, "Depth write");
+          ui.add(egui::Slider::new(&mut depth.clear_value, 0.0..=1.0).text("Depth clear"));
+
+          let camera = self.rasterization.camera_mut();
+          ui.horizontal(|ui| {
+            let orthographic = Projection::Orthographic {
+              height: 2.0,
+              near: 0.0,
+              far: 2.0,
+            };
+            let perspective = Projection::Perspective {
+              fov_y: std::f32::consts::FRAC_PI_2,
+              near: 0.1,
+              far: 10.0,
+            };
+            ui.selectable_value(&mut camera.projection, orthographic, "Orthographic");
+            ui.selectable_value(&mut camera.projection, perspective, "Perspective");
+          });
+          ui.add(egui::Slider::new(&mut camera.eye.x, -1.0..=1.0).text("Eye x"));
+          ui.add(egui::Slider::new(&mut camera.eye.y, -1.0..=1.0).text("Eye y"));
+          let target = self
+            .rasterization
+            .project_world_to_raster(self.rasterization.camera().target);
+          ui.label(format!("Target: ({:.1}, {:.1})", target.x, target.y));
           ui.label(format!(
             "Time: {:.1}s",
             self.start_time.elapsed().as_secs_f32()
@@ -281,7 +378,7 @@ impl<'window> State<'window> {
       let render_pass = &mut render_pass.forget_lifetime();
 
       render_pass.set_pipeline(&self.render_pipeline);
-      render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+      render_pass.set_bind_group(0, &self.raster_bind_group, &[]);
       render_pass.draw(0..3, 0..1);
 
       self